@@ -42,6 +42,103 @@ impl<'a> VmValueDecoder<'a> for VoterWeight {
     }
 }
 
+/// settings chosen by the creator when the topic is created
+#[derive(Encoder, Decoder, Default)]
+pub struct TopicConfig {
+    /// TOPIC_TYPE_BINARY, TOPIC_TYPE_MULTI_CHOICE, TOPIC_TYPE_RANKED or TOPIC_TYPE_APPROVAL
+    pub topic_type: u8,
+    /// the options of a multiple-choice, ranked or approval topic, empty for binary topics
+    pub options: Vec<Vec<u8>>,
+    /// SNAPSHOT_NONE, SNAPSHOT_AT_CREATION or SNAPSHOT_AT_FIRST_VOTE
+    pub snapshot_mode: u8,
+    /// QUORUM_NONE, QUORUM_ABSOLUTE or QUORUM_PERCENT
+    pub quorum_type: u8,
+    /// minimum participating weight, or percent of the total eligible weight for QUORUM_PERCENT
+    pub quorum: u64,
+    /// THRESHOLD_SIMPLE_MAJORITY or THRESHOLD_TWO_THIRDS
+    pub threshold: u8,
+    /// voters commit a hash of their ballot before end_time and reveal it before reveal_end_time
+    pub secret_ballot: bool,
+    /// end of the reveal window, only meaningful for secret ballot topics
+    pub reveal_end_time: u64,
+    /// only the voters set by the creator with setVoterForTopic can vote, with the weight
+    /// set by the creator instead of the weight in the peer pool
+    pub custom_voters: bool,
+    /// WEIGHT_STAKE, WEIGHT_ONE_NODE_ONE_VOTE, WEIGHT_INIT_POS or WEIGHT_SQRT_STAKE
+    pub weight_strategy: u8,
+    /// the calls made by executeTopic in order once the topic passed, empty if the topic
+    /// carries no action
+    pub actions: Vec<ProposalAction>,
+}
+
+/// a contract call carried by a topic
+#[derive(Encoder, Decoder)]
+pub struct ProposalAction {
+    /// VM_TYPE_WASM or VM_TYPE_NEO
+    pub vm_type: u8,
+    pub target: Address,
+    pub method: Vec<u8>,
+    /// the encoded args, appended to the method for WASM targets, a single bytearray
+    /// parameter for NEO targets
+    pub args: Vec<u8>,
+}
+
+/// the execution of the actions of a topic
+#[derive(Encoder, Decoder, Default)]
+pub struct TopicExecution {
+    /// the topic can not be executed before eta, 0 means the topic is not queued
+    pub eta: u64,
+    /// the admin or the guardian canceled the topic during the timelock
+    pub canceled: bool,
+    /// when executeTopic was invoked, 0 means not executed
+    pub executed_time: u64,
+    /// all the actions succeeded
+    pub success: bool,
+}
+
+/// result of the votes which is not covered by approve and reject
+#[derive(Encoder, Decoder, Default)]
+pub struct TopicTally {
+    /// weight of every option of a multiple-choice topic, indexed like TopicConfig.options
+    /// for ranked topics it is the weight of the first preferences, for approval topics every
+    /// approved option gets the full weight of the voter
+    pub options: Vec<u64>,
+    /// weight of the abstain votes, counts toward turnout but not toward the outcome
+    pub abstain: u64,
+    /// the sealed result, see get_topic_result, only valid when finalized_time is not 0
+    pub result: u8,
+    /// when finalizeTopic was invoked, 0 means not finalized
+    pub finalized_time: u64,
+    /// number of the nodes who approve, including the nodes who delegated to them
+    pub approve_count: u32,
+    /// number of the nodes who reject, including the nodes who delegated to them
+    pub reject_count: u32,
+    /// number of the nodes who abstain, including the nodes who delegated to them
+    pub abstain_count: u32,
+    /// number of the nodes who chose every option of a multiple-choice topic
+    pub option_counts: Vec<u32>,
+    /// the instant runoff rounds of a ranked topic, filled by finalizeTopic
+    pub rounds: Vec<RunoffRound>,
+    /// weight of all the votes which are not abstain
+    pub voted: u64,
+    /// number of the nodes who voted and did not abstain
+    pub voted_count: u32,
+    /// weight of all the votes including abstain, without the conviction multiplier so it can
+    /// be compared with the total eligible weight for the quorum
+    pub turnout: u64,
+}
+
+/// one round of the instant runoff of a ranked topic
+#[derive(Encoder, Decoder, Default)]
+pub struct RunoffRound {
+    /// weight of every option still in the runoff, 0 for the eliminated options
+    pub tallies: Vec<u64>,
+    /// weight of the ballots which rank none of the options still in the runoff
+    pub exhausted: u64,
+    /// the option eliminated after this round, empty in the last round
+    pub eliminated: Vec<u32>,
+}
+
 #[derive(Encoder, Decoder)]
 pub struct TopicInfo {
     pub gov_node_addr: Address,
@@ -54,6 +151,76 @@ pub struct TopicInfo {
     pub reject: u64,
     pub status: u8,
     pub hash: H256,
+    pub config: TopicConfig,
+    pub tally: TopicTally,
+    pub execution: TopicExecution,
+    /// number of the contract pauses already given back to the voting, see fold_pause_periods
+    pub pause_periods: u32,
+}
+
+/// TopicInfo layout stored under PRE_TOPIC_INFO before topic config existed
+#[derive(Encoder, Decoder)]
+pub struct LegacyTopicInfo {
+    pub gov_node_addr: Address,
+    pub topic_title: Vec<u8>,
+    pub topic_detail: Vec<u8>,
+    pub voters: Vec<VoterWeight>,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub approve: u64,
+    pub reject: u64,
+    pub status: u8,
+    pub hash: H256,
+}
+
+/// the existing queries keep returning the legacy layout
+impl From<TopicInfo> for LegacyTopicInfo {
+    fn from(info: TopicInfo) -> Self {
+        LegacyTopicInfo {
+            gov_node_addr: info.gov_node_addr,
+            topic_title: info.topic_title,
+            topic_detail: info.topic_detail,
+            voters: info.voters,
+            start_time: info.start_time,
+            end_time: info.end_time,
+            approve: info.approve,
+            reject: info.reject,
+            status: info.status,
+            hash: info.hash,
+        }
+    }
+}
+
+impl TopicInfo {
+    /// after this time no vote can change the tally any more
+    pub fn voting_end_time(&self) -> u64 {
+        if self.config.secret_ballot {
+            self.config.reveal_end_time
+        } else {
+            self.end_time
+        }
+    }
+}
+
+impl From<LegacyTopicInfo> for TopicInfo {
+    fn from(info: LegacyTopicInfo) -> Self {
+        TopicInfo {
+            gov_node_addr: info.gov_node_addr,
+            topic_title: info.topic_title,
+            topic_detail: info.topic_detail,
+            voters: info.voters,
+            start_time: info.start_time,
+            end_time: info.end_time,
+            approve: info.approve,
+            reject: info.reject,
+            status: info.status,
+            hash: info.hash,
+            config: TopicConfig::default(),
+            tally: TopicTally::default(),
+            execution: TopicExecution::default(),
+            pause_periods: 0,
+        }
+    }
 }
 
 impl<'a> VmValueDecoder<'a> for TopicInfo {
@@ -84,6 +251,10 @@ impl<'a> VmValueDecoder<'a> for TopicInfo {
             reject: reject as u64,
             status: status as u8,
             hash,
+            config: TopicConfig::default(),
+            tally: TopicTally::default(),
+            execution: TopicExecution::default(),
+            pause_periods: 0,
         })
     }
 }
@@ -93,6 +264,111 @@ pub struct VotedInfo {
     pub voter: Address,
     pub weight: u64,
     pub approve_or_reject: bool,
+    /// the chosen option index, only meaningful for multiple-choice topics
+    /// for ranked topics it is the first preference
+    pub option: u32,
+    /// the voter took part without backing any side, approve_or_reject and option are ignored
+    pub abstain: bool,
+    /// the weight of the nodes who delegated to this voter and did not vote themselves
+    pub delegated: Vec<VoterWeight>,
+    /// the node the voter authorized ONT to when a staker votes with its own stake,
+    /// zero address for the vote of a node
+    pub authorized_to: Address,
+    /// the ONT ID which cast the vote for the node, empty when the node voted with its address
+    pub ont_id: Vec<u8>,
+    /// the option indexes of a ranked topic, most preferred first
+    pub ranking: Vec<u32>,
+    /// the option indexes the voter approves in an approval topic
+    pub approvals: Vec<u32>,
+    /// percent of the weight given to approve, reject and abstain of a binary topic, or to
+    /// every option of a multiple-choice topic, empty if the weight is not split
+    pub shares: Vec<u32>,
+    /// the own weight is multiplied by conviction_multiplier, 0 means not locked
+    pub conviction: u8,
+    /// why the voter voted this way, empty for the votes cast without one
+    pub rationale: Rationale,
+}
+
+impl VotedInfo {
+    pub fn new(voter: Address, weight: u64) -> Self {
+        VotedInfo {
+            voter,
+            weight,
+            approve_or_reject: false,
+            option: 0,
+            abstain: false,
+            delegated: vec![],
+            authorized_to: Address::new([0u8; 20]),
+            ont_id: vec![],
+            ranking: vec![],
+            approvals: vec![],
+            shares: vec![],
+            conviction: 0,
+            rationale: Rationale::default(),
+        }
+    }
+
+    pub fn is_staker_vote(&self) -> bool {
+        self.authorized_to != Address::new([0u8; 20])
+    }
+
+    /// number of the nodes this vote stands for, stakers are not nodes
+    pub fn node_count(&self) -> u32 {
+        let own = if self.is_staker_vote() { 0 } else { 1 };
+        own + self.delegated.len() as u32
+    }
+
+    /// own weight multiplied by the conviction plus the delegated weight
+    pub fn total_weight(&self) -> u64 {
+        let delegated: U128 = self.delegated.iter().map(|d| d.weight).sum();
+        self.weight * conviction_multiplier(self.conviction) + delegated as u64
+    }
+
+    /// own weight plus the delegated weight, the weight this vote adds to the turnout
+    pub fn turnout_weight(&self) -> u64 {
+        let delegated: U128 = self.delegated.iter().map(|d| d.weight).sum();
+        self.weight + delegated as u64
+    }
+
+    /// a plain approve or reject of a node, nothing is lost in the LegacyVotedInfo layout
+    pub fn is_legacy(&self) -> bool {
+        !self.abstain
+            && self.option == 0
+            && self.delegated.is_empty()
+            && !self.is_staker_vote()
+            && self.ont_id.is_empty()
+            && self.ranking.is_empty()
+            && self.approvals.is_empty()
+            && self.shares.is_empty()
+            && self.conviction == 0
+            && self.rationale.is_empty()
+    }
+}
+
+/// VotedInfo layout stored under PRE_VOTED before multiple-choice topics existed
+#[derive(Encoder, Decoder)]
+pub struct LegacyVotedInfo {
+    pub voter: Address,
+    pub weight: u64,
+    pub approve_or_reject: bool,
+}
+
+impl From<LegacyVotedInfo> for VotedInfo {
+    fn from(info: LegacyVotedInfo) -> Self {
+        let mut voted_info = VotedInfo::new(info.voter, info.weight);
+        voted_info.approve_or_reject = info.approve_or_reject;
+        voted_info
+    }
+}
+
+impl From<VotedInfo> for LegacyVotedInfo {
+    fn from(info: VotedInfo) -> Self {
+        LegacyVotedInfo {
+            voter: info.voter,
+            weight: info.weight,
+            approve_or_reject: info.approve_or_reject,
+        }
+    }
 }
 
 impl<'a> VmValueDecoder<'a> for VotedInfo {
@@ -103,10 +379,149 @@ impl<'a> VmValueDecoder<'a> for VotedInfo {
         let addr_bytes = parser.bytearray()?;
         let addr = unsafe { *(addr_bytes.as_ptr() as *const Address) };
         let approve_or_reject = parser.bool()?;
-        Ok(VotedInfo {
-            voter: addr.clone(),
-            approve_or_reject,
-            weight: 0,
-        })
+        let mut voted_info = VotedInfo::new(addr.clone(), 0);
+        voted_info.approve_or_reject = approve_or_reject;
+        Ok(voted_info)
     }
 }
+
+/// the choice of a voter, used by the commit-reveal secret ballots
+#[derive(Encoder, Decoder)]
+pub struct Ballot {
+    /// VOTED_APPROVE, VOTED_REJECT, VOTED_OPTION, VOTED_ABSTAIN, VOTED_RANKED, VOTED_APPROVAL
+    /// or VOTED_SPLIT
+    pub choice: u8,
+    /// the chosen option index when choice is VOTED_OPTION
+    pub option: u32,
+    /// the option indexes, most preferred first, when choice is VOTED_RANKED
+    pub ranking: Vec<u32>,
+    /// the approved option indexes when choice is VOTED_APPROVAL
+    pub approvals: Vec<u32>,
+    /// the percent of the weight for every bucket when choice is VOTED_SPLIT, see VotedInfo
+    pub shares: Vec<u32>,
+    /// lock the vote after the topic ends for a longer multiplier, see conviction_multiplier
+    pub conviction: u8,
+    pub rationale: Rationale,
+}
+
+impl Ballot {
+    pub fn new(choice: u8) -> Self {
+        Ballot {
+            choice,
+            option: 0,
+            ranking: vec![],
+            approvals: vec![],
+            shares: vec![],
+            conviction: 0,
+            rationale: Rationale::default(),
+        }
+    }
+}
+
+/// the reason published with a vote, a short text or the hash and uri of a long text
+#[derive(Encoder, Decoder, Default, Clone)]
+pub struct Rationale {
+    pub text: Vec<u8>,
+    /// sha256 of the text found at uri, zero if there is no long text
+    pub content_hash: H256,
+    pub uri: Vec<u8>,
+}
+
+impl Rationale {
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty() && self.uri.is_empty() && self.content_hash == H256::repeat_byte(0)
+    }
+}
+
+/// the vote of the voter for the topic is locked until unlock_time
+#[derive(Encoder, Decoder)]
+pub struct VoteLock {
+    pub hash: H256,
+    /// the node of a staker vote, zero address for the vote of a node
+    pub authorized_to: Address,
+    pub conviction: u8,
+    /// the weight of the voter when the vote was cast, the voter must keep it until unlock_time
+    pub amount: u64,
+    pub unlock_time: u64,
+    /// the voter held less than amount before unlock_time, the multiplier does not count
+    pub forfeited: bool,
+}
+
+impl VoteLock {
+    pub fn is_lock_of(&self, hash: &H256, authorized_to: &Address) -> bool {
+        &self.hash == hash && &self.authorized_to == authorized_to
+    }
+}
+
+/// the admins of the contract, threshold of them must confirm every admin proposal
+#[derive(Encoder, Decoder)]
+pub struct AdminSet {
+    pub admins: Vec<Address>,
+    pub threshold: u32,
+}
+
+/// an admin transfer proposed by the admins and waiting for the new admin to accept it
+#[derive(Encoder, Decoder)]
+pub struct PendingAdmin {
+    pub new_admin: Address,
+    /// the admin leaving the set, zero address if the new admin is added
+    pub replaced: Address,
+}
+
+/// a privileged action waiting for the confirmations of the admins
+#[derive(Encoder, Decoder)]
+pub struct AdminProposal {
+    pub id: u64,
+    pub proposer: Address,
+    /// one of ADMIN_METHODS
+    pub method: Vec<u8>,
+    /// the args of the method encoded like the args of the action in invoke
+    pub args: Vec<u8>,
+    /// the admins who confirmed, the proposer included
+    pub confirmations: Vec<Address>,
+    /// the proposal can not be confirmed at or after expiry
+    pub expiry: u64,
+    pub executed: bool,
+}
+
+/// a pause of the whole contract, see setPaused
+#[derive(Encoder, Decoder)]
+pub struct PausePeriod {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// who vetoed a passed topic and why
+#[derive(Encoder, Decoder)]
+pub struct Veto {
+    pub guardian: Address,
+    pub reason: Vec<u8>,
+    pub time: u64,
+}
+
+#[derive(Encoder, Decoder)]
+pub struct Commitment {
+    pub voter: Address,
+    pub commitment: H256,
+    pub revealed: bool,
+}
+
+#[derive(Encoder, Decoder)]
+pub struct Delegation {
+    pub delegator: Address,
+    pub delegate: Address,
+}
+
+/// the global delegation of the delegator from time on, zero delegate after a revocation
+#[derive(Encoder, Decoder)]
+pub struct DelegationChange {
+    pub delegate: Address,
+    pub time: u64,
+}
+
+/// every change of the global delegation of a delegator, oldest first
+#[derive(Encoder, Decoder)]
+pub struct DelegationHistory {
+    pub delegator: Address,
+    pub changes: Vec<DelegationChange>,
+}
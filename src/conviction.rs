@@ -0,0 +1,162 @@
+use super::*;
+
+/// the base lock period of a conviction vote, 7 days
+pub const LOCK_PERIOD: u64 = 7 * 24 * 3600;
+pub const MAX_CONVICTION: u8 = 5;
+
+/// conviction 0 is not locked and has weight 1x, conviction n multiplies the weight by n + 1
+/// and locks the vote for 2^(n-1) lock periods after the voting of the topic ends
+pub fn conviction_multiplier(conviction: u8) -> u64 {
+    conviction as u64 + 1
+}
+
+pub fn conviction_lock_periods(conviction: u8) -> u64 {
+    if conviction == 0 {
+        return 0;
+    }
+    1 << (conviction - 1)
+}
+
+/// record the lock of the vote, or remove it if the voter changed the vote to conviction 0
+/// a voter who forfeited a lock can not vote with a conviction before that lock ends
+pub fn update_vote_lock(hash: &H256, topic_info: &TopicInfo, vi: &VotedInfo) {
+    if vi.conviction == 0 {
+        remove_vote_lock(hash, &vi.voter, &vi.authorized_to);
+        return;
+    }
+    let mut locks = get_vote_locks(&vi.voter);
+    let cur = timestamp();
+    assert!(!locks.iter().any(|l| l.forfeited && l.unlock_time > cur));
+    locks.retain(|l| !l.is_lock_of(hash, &vi.authorized_to));
+    let lock_time = conviction_lock_periods(vi.conviction) * LOCK_PERIOD;
+    locks.push(VoteLock {
+        hash: hash.clone(),
+        authorized_to: vi.authorized_to,
+        conviction: vi.conviction,
+        amount: vi.weight,
+        unlock_time: topic_info.voting_end_time() + lock_time,
+        forfeited: false,
+    });
+    put_vote_locks(&vi.voter, locks);
+}
+
+/// drop the lock of a vote which was revoked or changed to conviction 0
+pub fn remove_vote_lock(hash: &H256, voter: &Address, authorized_to: &Address) {
+    let mut locks = get_vote_locks(voter);
+    let len = locks.len();
+    locks.retain(|l| !l.is_lock_of(hash, authorized_to));
+    if locks.len() != len {
+        put_vote_locks(voter, locks);
+    }
+}
+
+/// the weight the voter holds now, a staker's ONT authorized to the node or the node's own
+/// weight, read live even if the topic uses a weight snapshot
+fn live_lock_weight(info: &TopicInfo, voter: &Address, lock: &VoteLock) -> u64 {
+    if lock.authorized_to != Address::new([0u8; 20]) {
+        get_authorized_weight(&lock.authorized_to, voter)
+    } else {
+        get_voter_weight_by_strategy(voter, info.config.weight_strategy)
+    }
+}
+
+/// called whenever the weight of a vote is recomputed, the own weight counts at most the
+/// locked amount, and the multiplier is forfeited once the voter holds less than the amount
+pub fn enforce_vote_lock(hash: &H256, info: &TopicInfo, vi: &mut VotedInfo) {
+    if vi.conviction == 0 {
+        return;
+    }
+    let mut locks = get_vote_locks(&vi.voter);
+    let pos = match locks
+        .iter()
+        .position(|l| l.is_lock_of(hash, &vi.authorized_to))
+    {
+        Some(pos) => pos,
+        None => {
+            vi.conviction = 0;
+            return;
+        }
+    };
+    if !locks[pos].forfeited && live_lock_weight(info, &vi.voter, &locks[pos]) < locks[pos].amount {
+        locks[pos].forfeited = true;
+        put_vote_locks(&vi.voter, locks);
+        notify_forfeit(hash, &vi.voter, &vi.authorized_to);
+        vi.conviction = 0;
+        return;
+    }
+    if locks[pos].forfeited {
+        vi.conviction = 0;
+    } else {
+        vi.weight = vi.weight.min(locks[pos].amount);
+    }
+}
+
+/// anyone can report a voter who holds less than the locked amount before unlock_time
+/// the lock is forfeited, the multiplier no longer counts for a topic which is not finalized
+/// and the voter can not vote with a conviction before unlock_time
+pub fn check_vote_lock(hash: &H256, voter: Address, authorized_to: Address) -> bool {
+    let info = get_topic_info(hash).expect("not exist topic info");
+    let mut locks = get_vote_locks(&voter);
+    let pos = locks
+        .iter()
+        .position(|l| l.is_lock_of(hash, &authorized_to))
+        .expect("the vote is not locked");
+    let lock = &locks[pos];
+    if lock.forfeited || timestamp() >= lock.unlock_time {
+        return false;
+    }
+    if live_lock_weight(&info, &voter, lock) >= lock.amount {
+        return false;
+    }
+    locks[pos].forfeited = true;
+    put_vote_locks(&voter, locks);
+    notify_forfeit(hash, &voter, &authorized_to);
+    true
+}
+
+fn notify_forfeit(hash: &H256, voter: &Address, authorized_to: &Address) {
+    EventBuilder::new()
+        .string("forfeitVoteLock")
+        .h256(hash)
+        .address(voter)
+        .address(authorized_to)
+        .notify();
+}
+
+/// release the lock of the vote after unlock_time, or at any time if the topic was canceled
+/// authorized_to is the node of a staker vote, zero address for the vote of a node
+pub fn unlock_vote(hash: &H256, voter: Address, authorized_to: Address) -> bool {
+    assert!(check_witness(&voter));
+    let mut locks = get_vote_locks(&voter);
+    let pos = locks
+        .iter()
+        .position(|l| l.is_lock_of(hash, &authorized_to))
+        .expect("the vote is not locked");
+    let canceled = get_topic_info(hash).map_or(false, |info| info.status == 0);
+    assert!(canceled || timestamp() >= locks[pos].unlock_time);
+    locks.remove(pos);
+    put_vote_locks(&voter, locks);
+    EventBuilder::new()
+        .string("unlockVote")
+        .h256(hash)
+        .address(&voter)
+        .address(&authorized_to)
+        .notify();
+    true
+}
+
+pub fn get_vote_lock(hash: &H256, voter: &Address, authorized_to: &Address) -> Option<VoteLock> {
+    get_vote_locks(voter)
+        .into_iter()
+        .find(|l| l.is_lock_of(hash, authorized_to))
+}
+
+pub fn get_vote_locks(voter: &Address) -> Vec<VoteLock> {
+    let key = get_key(PRE_VOTE_LOCKS, voter.as_ref());
+    database::get::<_, Vec<VoteLock>>(key).unwrap_or(vec![])
+}
+
+fn put_vote_locks(voter: &Address, locks: Vec<VoteLock>) {
+    let key = get_key(PRE_VOTE_LOCKS, voter.as_ref());
+    database::put(key, locks);
+}
@@ -0,0 +1,220 @@
+use super::*;
+
+/// delegate the vote of a governance node to another one for all the topics
+/// the delegate's choice is counted with the delegator's weight unless the delegator votes
+pub fn delegate_vote(delegator: Address, delegate: Address) -> bool {
+    check_delegation(&delegator, &delegate);
+    let mut delegations = get_delegations();
+    set_delegation(&mut delegations, delegator, delegate);
+    assert!(!has_cycle(&delegations, &delegator));
+    database::put(KEY_DELEGATIONS, delegations);
+    record_delegation_change(delegator, delegate);
+    EventBuilder::new()
+        .string("delegateVote")
+        .address(&delegator)
+        .address(&delegate)
+        .notify();
+    true
+}
+
+pub fn revoke_delegation(delegator: Address) -> bool {
+    assert!(check_witness(&delegator));
+    let mut delegations = get_delegations();
+    let len = delegations.len();
+    delegations.retain(|d| d.delegator != delegator);
+    assert_ne!(delegations.len(), len, "no delegation");
+    database::put(KEY_DELEGATIONS, delegations);
+    record_delegation_change(delegator, Address::new([0u8; 20]));
+    EventBuilder::new()
+        .string("revokeDelegation")
+        .address(&delegator)
+        .notify();
+    true
+}
+
+/// delegate the vote for one topic, overrides the global delegation of the delegator
+pub fn delegate_topic_vote(hash: &H256, delegator: Address, delegate: Address) -> bool {
+    check_delegation(&delegator, &delegate);
+    let info = get_topic_info(hash).expect("not exist topic info");
+    assert!(info.status == 1);
+    assert!(info.voting_end_time() > timestamp());
+    let mut delegations = get_topic_delegations(hash);
+    set_delegation(&mut delegations, delegator, delegate);
+    put_topic_delegations(hash, delegations);
+    assert!(!has_cycle(&get_effective_delegations(hash), &delegator));
+    EventBuilder::new()
+        .string("delegateTopicVote")
+        .h256(hash)
+        .address(&delegator)
+        .address(&delegate)
+        .notify();
+    true
+}
+
+pub fn revoke_topic_delegation(hash: &H256, delegator: Address) -> bool {
+    assert!(check_witness(&delegator));
+    let info = get_topic_info(hash).expect("not exist topic info");
+    assert!(info.voting_end_time() > timestamp());
+    let mut delegations = get_topic_delegations(hash);
+    let len = delegations.len();
+    delegations.retain(|d| d.delegator != delegator);
+    assert_ne!(delegations.len(), len, "no delegation");
+    put_topic_delegations(hash, delegations);
+    EventBuilder::new()
+        .string("revokeTopicDelegation")
+        .h256(hash)
+        .address(&delegator)
+        .notify();
+    true
+}
+
+pub fn get_delegations() -> Vec<Delegation> {
+    database::get::<_, Vec<Delegation>>(KEY_DELEGATIONS).unwrap_or(vec![])
+}
+
+pub fn get_topic_delegations(hash: &H256) -> Vec<Delegation> {
+    let key = get_key(PRE_TOPIC_DELEGATIONS, hash.as_ref());
+    database::get::<_, Vec<Delegation>>(key).unwrap_or(vec![])
+}
+
+/// the global delegations overridden by the delegations of the topic
+/// once the voting of the topic ended the global delegations are the ones made before its end
+pub fn get_effective_delegations(hash: &H256) -> Vec<Delegation> {
+    let info = get_topic_info(hash).expect("not exist topic info");
+    let end = info.voting_end_time();
+    let mut delegations = if timestamp() < end {
+        get_delegations()
+    } else {
+        delegations_at(&get_delegation_history(), end)
+    };
+    for d in get_topic_delegations(hash) {
+        set_delegation(&mut delegations, d.delegator, d.delegate);
+    }
+    delegations
+}
+
+pub fn get_delegation_history() -> Vec<DelegationHistory> {
+    database::get::<_, Vec<DelegationHistory>>(KEY_DELEGATION_HISTORY).unwrap_or(vec![])
+}
+
+/// the global delegations as they were just before time
+pub fn delegations_at(history: &[DelegationHistory], time: u64) -> Vec<Delegation> {
+    let mut delegations = vec![];
+    for h in history.iter() {
+        if let Some(c) = h.changes.iter().rev().find(|c| c.time < time) {
+            if c.delegate != Address::new([0u8; 20]) {
+                delegations.push(Delegation {
+                    delegator: h.delegator,
+                    delegate: c.delegate,
+                });
+            }
+        }
+    }
+    delegations
+}
+
+fn record_delegation_change(delegator: Address, delegate: Address) {
+    let mut history = get_delegation_history();
+    let change = DelegationChange {
+        delegate,
+        time: timestamp(),
+    };
+    match history.iter_mut().find(|h| h.delegator == delegator) {
+        Some(h) => h.changes.push(change),
+        None => history.push(DelegationHistory {
+            delegator,
+            changes: vec![change],
+        }),
+    }
+    database::put(KEY_DELEGATION_HISTORY, history);
+}
+
+/// give the weight of every delegator who did not vote to the voter at the end of its
+/// delegation chain, delegators without weight or whose chain ends in a cycle or a node who
+/// did not vote are not counted
+pub fn apply_delegations(hash: &H256, info: &TopicInfo, voted_info: &mut [VotedInfo]) {
+    for i in voted_info.iter_mut() {
+        i.delegated = vec![];
+    }
+    let delegations = get_effective_delegations(hash);
+    for d in delegations.iter() {
+        if voted_info
+            .iter()
+            .any(|i| i.voter == d.delegator && !i.is_staker_vote())
+        {
+            continue;
+        }
+        if let Some(pos) = resolve_delegate(&delegations, &d.delegator, voted_info) {
+            let weight = get_topic_voter_weight(hash, info, &d.delegator)
+                .saturating_sub(get_staker_voted_weight(voted_info, &d.delegator));
+            // a delegator without weight is not counted as a node either
+            if weight == 0 {
+                continue;
+            }
+            voted_info[pos].delegated.push(VoterWeight {
+                voter: d.delegator,
+                weight: weight as U128,
+            });
+        }
+    }
+}
+
+/// follow the delegation chain of the delegator, returns the position of the first voter
+pub fn resolve_delegate(
+    delegations: &[Delegation],
+    delegator: &Address,
+    voted_info: &[VotedInfo],
+) -> Option<usize> {
+    let mut visited = vec![*delegator];
+    let mut cur = *delegator;
+    loop {
+        let next = delegations.iter().find(|d| d.delegator == cur)?.delegate;
+        if let Some(pos) = voted_info
+            .iter()
+            .position(|i| i.voter == next && !i.is_staker_vote())
+        {
+            return Some(pos);
+        }
+        if visited.contains(&next) {
+            return None;
+        }
+        visited.push(next);
+        cur = next;
+    }
+}
+
+/// whether following the delegation chain from the delegator leads back to it
+pub fn has_cycle(delegations: &[Delegation], delegator: &Address) -> bool {
+    let mut visited = vec![*delegator];
+    let mut cur = *delegator;
+    while let Some(d) = delegations.iter().find(|d| d.delegator == cur) {
+        if visited.contains(&d.delegate) {
+            return true;
+        }
+        visited.push(d.delegate);
+        cur = d.delegate;
+    }
+    false
+}
+
+fn check_delegation(delegator: &Address, delegate: &Address) {
+    assert!(check_witness(delegator));
+    assert!(is_gov_node(delegator));
+    assert!(is_gov_node(delegate));
+    assert_ne!(delegator, delegate);
+}
+
+fn set_delegation(delegations: &mut Vec<Delegation>, delegator: Address, delegate: Address) {
+    match delegations.iter_mut().find(|d| d.delegator == delegator) {
+        Some(d) => d.delegate = delegate,
+        None => delegations.push(Delegation {
+            delegator,
+            delegate,
+        }),
+    }
+}
+
+fn put_topic_delegations(hash: &H256, delegations: Vec<Delegation>) {
+    let key = get_key(PRE_TOPIC_DELEGATIONS, hash.as_ref());
+    database::put(key, delegations);
+}
@@ -0,0 +1,136 @@
+use super::*;
+use ostd::runtime::call_contract;
+
+pub const VM_TYPE_WASM: u8 = 0;
+pub const VM_TYPE_NEO: u8 = 1;
+
+/// the delay between the end of the voting and the execution when the admin did not set one
+pub const DEFAULT_TIMELOCK_DELAY: u64 = 2 * 24 * 3600;
+pub const MIN_TIMELOCK_DELAY: u64 = 24 * 3600;
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 3600;
+
+pub fn check_actions(actions: &[ProposalAction]) {
+    for action in actions.iter() {
+        assert!(action.vm_type <= VM_TYPE_NEO);
+        assert_ne!(&action.target, &Address::new([0u8; 20]));
+        assert!(!action.method.is_empty());
+        if action.vm_type == VM_TYPE_NEO {
+            assert!(core::str::from_utf8(&action.method).is_ok());
+        }
+    }
+}
+
+pub fn get_timelock_delay() -> u64 {
+    database::get::<_, u64>(KEY_TIMELOCK_DELAY).unwrap_or(DEFAULT_TIMELOCK_DELAY)
+}
+
+/// only an admin proposal can invoke, the delay applies to the topics queued afterwards
+pub fn set_timelock_delay(delay: u64) -> bool {
+    assert!(delay >= MIN_TIMELOCK_DELAY && delay <= MAX_TIMELOCK_DELAY);
+    database::put(KEY_TIMELOCK_DELAY, delay);
+    EventBuilder::new()
+        .string("setTimelockDelay")
+        .number(delay as U128)
+        .notify();
+    true
+}
+
+/// hashes of the passed topics waiting for their eta or for executeTopic
+pub fn get_queued_topics() -> Vec<H256> {
+    database::get::<_, Vec<H256>>(KEY_QUEUED_TOPICS).unwrap_or(vec![])
+}
+
+/// called when a topic with actions passes, the topic is executable after the delay and
+/// after the guardians can no longer veto it
+pub fn queue_topic(hash: &H256, info: &mut TopicInfo) {
+    let eta = info.voting_end_time() + get_timelock_delay();
+    info.execution.eta = eta.max(veto_deadline(info));
+    let mut queued = get_queued_topics();
+    queued.push(hash.clone());
+    database::put(KEY_QUEUED_TOPICS, queued);
+    EventBuilder::new()
+        .string("queueTopic")
+        .h256(hash)
+        .number(info.execution.eta as U128)
+        .notify();
+}
+
+pub fn dequeue_topic(hash: &H256) {
+    let mut queued = get_queued_topics();
+    queued.retain(|h| h != hash);
+    database::put(KEY_QUEUED_TOPICS, queued);
+}
+
+/// cancel a queued topic before its eta, only a guardian can invoke
+/// the admins cancel it with an admin proposal
+/// the sealed result becomes RESULT_EXECUTION_CANCELED
+pub fn cancel_queued_topic(hash: &H256, guardian: Address) -> bool {
+    assert!(is_guardian(&guardian));
+    assert!(check_witness(&guardian));
+    cancel_queued(hash)
+}
+
+pub fn cancel_queued(hash: &H256) -> bool {
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    assert_ne!(info.execution.eta, 0);
+    assert_eq!(info.execution.executed_time, 0);
+    assert!(!info.execution.canceled);
+    assert!(timestamp() < info.execution.eta, "timelock expired");
+    info.execution.canceled = true;
+    info.tally.result = RESULT_EXECUTION_CANCELED;
+    put_topic_info(hash, info);
+    dequeue_topic(hash);
+    EventBuilder::new()
+        .string("cancelQueuedTopic")
+        .h256(hash)
+        .notify();
+    true
+}
+
+/// run the actions of a passed topic after its eta, all user can invoke
+/// the actions run only once, the execution records whether all of them succeeded
+pub fn execute_topic(hash: &H256) -> bool {
+    assert!(!is_paused());
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    assert!(!info.config.actions.is_empty());
+    assert_eq!(info.execution.executed_time, 0, "already executed");
+    assert!(!info.execution.canceled);
+    assert_ne!(info.tally.finalized_time, 0);
+    assert_eq!(info.tally.result, RESULT_PASSED, "topic not passed");
+    let cur = timestamp();
+    assert!(cur >= info.execution.eta, "timelock not expired");
+    // the veto period may have grown since the topic was queued
+    assert!(cur >= veto_deadline(&info), "veto period not ended");
+    // mark the topic executed before the calls so a target can not execute it again
+    info.execution.executed_time = cur;
+    put_topic_info(hash, info);
+    dequeue_topic(hash);
+    let mut info = get_topic_info(hash).unwrap();
+    let success = info.config.actions.iter().all(|a| call_action(a));
+    info.execution.success = success;
+    put_topic_info(hash, info);
+    EventBuilder::new()
+        .string("executeTopic")
+        .h256(hash)
+        .bool(success)
+        .number(cur as U128)
+        .notify();
+    true
+}
+
+/// invoke the target contract, true if the call returned
+fn call_action(action: &ProposalAction) -> bool {
+    match action.vm_type {
+        VM_TYPE_WASM => {
+            let mut sink = Sink::new(64);
+            sink.write(action.method.as_slice());
+            let mut input = sink.bytes().to_vec();
+            input.extend_from_slice(&action.args);
+            call_contract(&action.target, &input).is_some()
+        }
+        _ => {
+            let method = core::str::from_utf8(&action.method).unwrap();
+            neo::call_contract(&action.target, (method, (action.args.as_slice(),))).is_some()
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+/// the guardians can veto a passed topic within the veto period after the voting ends
+pub const DEFAULT_VETO_PERIOD: u64 = 7 * 24 * 3600;
+pub const MAX_VETO_PERIOD: u64 = 30 * 24 * 3600;
+pub const MAX_VETO_REASON_LEN: usize = 1024;
+
+/// the security council managed by the admin
+pub fn get_guardians() -> Vec<Address> {
+    database::get::<_, Vec<Address>>(KEY_GUARDIANS).unwrap_or(vec![])
+}
+
+/// only an admin proposal can invoke
+pub fn add_guardian(guardian: &Address) -> bool {
+    let mut guardians = get_guardians();
+    assert!(!guardians.contains(guardian));
+    guardians.push(guardian.clone());
+    database::put(KEY_GUARDIANS, guardians);
+    EventBuilder::new()
+        .string("addGuardian")
+        .address(guardian)
+        .notify();
+    true
+}
+
+/// only an admin proposal can invoke
+pub fn remove_guardian(guardian: &Address) -> bool {
+    let mut guardians = get_guardians();
+    let pos = guardians
+        .iter()
+        .position(|g| g == guardian)
+        .expect("not a guardian");
+    guardians.remove(pos);
+    database::put(KEY_GUARDIANS, guardians);
+    EventBuilder::new()
+        .string("removeGuardian")
+        .address(guardian)
+        .notify();
+    true
+}
+
+pub fn get_veto_period() -> u64 {
+    database::get::<_, u64>(KEY_VETO_PERIOD).unwrap_or(DEFAULT_VETO_PERIOD)
+}
+
+/// only an admin proposal can invoke, topics can not be executed before the period ends
+pub fn set_veto_period(period: u64) -> bool {
+    assert!(period <= MAX_VETO_PERIOD);
+    database::put(KEY_VETO_PERIOD, period);
+    EventBuilder::new()
+        .string("setVetoPeriod")
+        .number(period as U128)
+        .notify();
+    true
+}
+
+/// the guardians can veto the topic before this time
+pub fn veto_deadline(info: &TopicInfo) -> u64 {
+    info.voting_end_time() + get_veto_period()
+}
+
+pub fn is_guardian(caller: &Address) -> bool {
+    get_guardians().contains(caller)
+}
+
+/// overturn a passed topic which is not executed yet, only a guardian can invoke
+/// the sealed result becomes RESULT_VETOED and the topic leaves the timelock queue
+pub fn veto_topic(hash: &H256, guardian: Address, reason: &[u8]) -> bool {
+    assert!(is_guardian(&guardian));
+    assert!(check_witness(&guardian));
+    assert!(reason.len() <= MAX_VETO_REASON_LEN);
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    assert_ne!(info.tally.finalized_time, 0);
+    assert_eq!(info.tally.result, RESULT_PASSED);
+    assert_eq!(info.execution.executed_time, 0);
+    let cur = timestamp();
+    assert!(cur < veto_deadline(&info), "veto period ended");
+    info.tally.result = RESULT_VETOED;
+    put_topic_info(hash, info);
+    dequeue_topic(hash);
+    let veto = Veto {
+        guardian,
+        reason: reason.to_vec(),
+        time: cur,
+    };
+    database::put(get_key(PRE_VETO, hash.as_ref()), veto);
+    EventBuilder::new()
+        .string("vetoTopic")
+        .h256(hash)
+        .address(&guardian)
+        .bytearray(reason)
+        .notify();
+    true
+}
+
+pub fn get_topic_veto(hash: &H256) -> Option<Veto> {
+    database::get::<_, Veto>(get_key(PRE_VETO, hash.as_ref()))
+}
@@ -18,11 +18,89 @@ const PRE_VOTED: &[u8] = b"03";
 const PRE_TOPIC_HASH: &[u8] = b"04";
 const KEY_CUR_HASH_NUM: &[u8] = b"05";
 const KEY_ADMIN: &[u8] = b"06";
+const PRE_TOPIC_INFO_V2: &[u8] = b"07";
+const PRE_VOTED_V2: &[u8] = b"08";
+const PRE_WEIGHT_SNAPSHOT: &[u8] = b"09";
+const PRE_COMMITMENT: &[u8] = b"10";
+const KEY_DELEGATIONS: &[u8] = b"11";
+const PRE_TOPIC_DELEGATIONS: &[u8] = b"12";
+const PRE_ONT_ID_NODE: &[u8] = b"13";
+const PRE_VOTE_LOCKS: &[u8] = b"14";
+const KEY_GUARDIANS: &[u8] = b"15";
+const KEY_TIMELOCK_DELAY: &[u8] = b"16";
+const KEY_QUEUED_TOPICS: &[u8] = b"17";
+const PRE_VETO: &[u8] = b"18";
+const KEY_ADMINS: &[u8] = b"19";
+const KEY_ADMIN_PROPOSAL_NUM: &[u8] = b"20";
+const PRE_ADMIN_PROPOSAL: &[u8] = b"21";
+const PRE_ROLE_MEMBERS: &[u8] = b"22";
+const KEY_PAUSED: &[u8] = b"23";
+const KEY_PENDING_ADMIN: &[u8] = b"24";
+const PRE_TOPIC_PAUSED: &[u8] = b"25";
+const KEY_DELEGATION_HISTORY: &[u8] = b"26";
+const KEY_VETO_PERIOD: &[u8] = b"27";
+const KEY_PAUSE_PERIODS: &[u8] = b"28";
+
+const MAX_RATIONALE_LEN: usize = 256;
+
+const TOPIC_TYPE_BINARY: u8 = 0;
+const TOPIC_TYPE_MULTI_CHOICE: u8 = 1;
+const TOPIC_TYPE_RANKED: u8 = 2;
+const TOPIC_TYPE_APPROVAL: u8 = 3;
+
+/// recompute the voter weights from the peer pool on every vote
+const SNAPSHOT_NONE: u8 = 0;
+/// pin the voter weights when the topic is created
+const SNAPSHOT_AT_CREATION: u8 = 1;
+/// pin the voter weights at the first vote after start_time
+const SNAPSHOT_AT_FIRST_VOTE: u8 = 2;
+
+/// init_pos + total_pos of the node
+const WEIGHT_STAKE: u8 = 0;
+/// every consensus and candidate node has weight 1
+const WEIGHT_ONE_NODE_ONE_VOTE: u8 = 1;
+/// only the init_pos of the node
+const WEIGHT_INIT_POS: u8 = 2;
+/// square root of init_pos + total_pos, damps the weight of the big nodes
+const WEIGHT_SQRT_STAKE: u8 = 3;
+
+const VOTED_NONE: u8 = 0;
+const VOTED_APPROVE: u8 = 1;
+const VOTED_REJECT: u8 = 2;
+const VOTED_OPTION: u8 = 3;
+const VOTED_ABSTAIN: u8 = 4;
+const VOTED_RANKED: u8 = 5;
+const VOTED_APPROVAL: u8 = 6;
+const VOTED_SPLIT: u8 = 7;
 
 const ADMIN: Address = base58!("Aejfo7ZX5PVpenRj23yChnyH64nf8T1zbu");
 
 mod basic;
 use basic::*;
+mod result;
+use result::*;
+mod secret;
+use secret::*;
+mod delegation;
+use delegation::*;
+mod staker;
+use staker::*;
+mod ontid;
+use ontid::*;
+mod ranked;
+use ranked::*;
+mod conviction;
+use conviction::*;
+mod execution;
+use execution::*;
+mod guardian;
+use guardian::*;
+mod multisig;
+use multisig::*;
+mod role;
+use role::*;
+mod pause;
+use pause::*;
 
 #[cfg(test)]
 mod test;
@@ -32,18 +110,8 @@ mod test;
 //main AJGFd2yV4RX3iWEBQDoGbWkXVGiM9qX4Ee
 const NEO_VOTE_CONTRACT_ADDRESS: Address = base58!("AKzJGcCVr9wVEG95XvP3VnCDRivVjo391r");
 
-fn get_admin() -> Address {
-    database::get::<_, Address>(KEY_ADMIN).unwrap_or(ADMIN)
-}
-
-fn update_admin(new_admin: &Address) -> bool {
-    let admin = get_admin();
-    assert!(check_witness(&admin));
-    database::put(KEY_ADMIN, new_admin);
-    true
-}
-
-/// upgrade contract, only admin has the right to invoke this method
+/// upgrade contract, only an admin proposal confirmed by enough admins who are upgraders can
+/// invoke this method
 fn migrate(
     code: &[u8],
     vm_ty: U128,
@@ -53,8 +121,6 @@ fn migrate(
     email: &str,
     desc: &str,
 ) -> bool {
-    let admin = get_admin();
-    assert!(check_witness(&admin));
     let addr = contract_migrate(code, vm_ty as u32, name, version, author, email, desc);
     assert_ne!(addr, Address::new([0u8; 20]));
     true
@@ -83,21 +149,75 @@ fn create_topic(
     topic_detail: &[u8],
     start_time: U128,
     end_time: U128,
+) -> bool {
+    create_topic_with_config(
+        gov_node_addr,
+        topic_title,
+        topic_detail,
+        start_time,
+        end_time,
+        TopicConfig::default(),
+    )
+}
+
+/// create topic with the given config, e.g. a multiple-choice topic
+fn create_topic_with_config(
+    gov_node_addr: Address,
+    topic_title: &[u8],
+    topic_detail: &[u8],
+    start_time: U128,
+    end_time: U128,
+    config: TopicConfig,
 ) -> bool {
     assert!(check_witness(&gov_node_addr));
+    create_topic_inner(
+        gov_node_addr,
+        topic_title,
+        topic_detail,
+        start_time,
+        end_time,
+        config,
+    )
+}
+
+/// create topic for the gov node, the caller must have checked the gov node authorized it
+fn create_topic_inner(
+    gov_node_addr: Address,
+    topic_title: &[u8],
+    topic_detail: &[u8],
+    start_time: U128,
+    end_time: U128,
+    config: TopicConfig,
+) -> bool {
+    assert!(!is_paused());
     assert!(is_gov_node(&gov_node_addr));
     assert!(start_time < end_time);
     let cur = timestamp() as U128;
     assert!(cur < end_time);
+    if config.custom_voters {
+        // the creator sets the voters with setVoterForTopic before the topic starts
+        assert!(cur < start_time);
+    }
+    check_topic_config(&config);
+    if config.secret_ballot {
+        assert!(config.reveal_end_time as U128 > end_time);
+    }
 
     let hash = current_txhash();
+    if config.snapshot_mode == SNAPSHOT_AT_CREATION {
+        take_weight_snapshot(&hash, config.weight_strategy);
+    }
     let tc = Topic {
         topic_title: topic_title.to_vec(),
         topic_detail: topic_detail.to_vec(),
     };
     let key_topic = get_key(PRE_TOPIC, hash.as_ref());
     database::put(key_topic, tc);
-    let key_topic_info = get_key(PRE_TOPIC_INFO, hash.as_ref());
+    let tally = TopicTally {
+        options: vec![0; config.options.len()],
+        option_counts: vec![0; config.options.len()],
+        ..TopicTally::default()
+    };
     let info = TopicInfo {
         gov_node_addr,
         topic_title: topic_title.to_vec(),
@@ -109,8 +229,13 @@ fn create_topic(
         reject: 0,
         status: 1, // 1 means valid, 0 means invalid
         hash: hash.clone(),
+        config,
+        tally,
+        execution: TopicExecution::default(),
+        // the pauses before the creation are not given to the topic
+        pause_periods: get_pause_periods().len() as u32,
     };
-    database::put(key_topic_info, info);
+    put_topic_info(&hash, info);
     let next_hash_key = get_current_hash_num();
     database::put(
         get_key(PRE_TOPIC_HASH, next_hash_key.to_string().as_bytes()),
@@ -126,6 +251,143 @@ fn create_topic(
     true
 }
 
+fn check_topic_config(config: &TopicConfig) {
+    match config.topic_type {
+        TOPIC_TYPE_BINARY => assert!(config.options.is_empty()),
+        TOPIC_TYPE_MULTI_CHOICE | TOPIC_TYPE_RANKED | TOPIC_TYPE_APPROVAL => {
+            assert!(config.options.len() >= 2)
+        }
+        _ => panic!("unknown topic type"),
+    }
+    assert!(config.snapshot_mode <= SNAPSHOT_AT_FIRST_VOTE);
+    assert!(config.weight_strategy <= WEIGHT_SQRT_STAKE);
+    check_result_rule(config);
+    check_actions(&config.actions);
+}
+
+/// pin the weight of every consensus and candidate node for the topic
+fn take_weight_snapshot(hash: &H256, strategy: u8) {
+    let peer_pool_map = get_peer_pool();
+    let mut snapshot: Vec<VoterWeight> = Vec::with_capacity(peer_pool_map.peer_pool_map.len());
+    for item in peer_pool_map.peer_pool_map.iter() {
+        if item.status != 1 && item.status != 2 {
+            continue;
+        }
+        snapshot.push(VoterWeight {
+            voter: item.peer_pubkey_addr,
+            weight: strategy_weight(strategy, item.init_pos, item.total_pos) as U128,
+        });
+    }
+    let key = get_key(PRE_WEIGHT_SNAPSHOT, hash.as_ref());
+    database::put(key, snapshot);
+}
+
+fn get_weight_snapshot(hash: &H256) -> Option<Vec<VoterWeight>> {
+    let key = get_key(PRE_WEIGHT_SNAPSHOT, hash.as_ref());
+    database::get::<_, Vec<VoterWeight>>(key)
+}
+
+/// the sum of the weight of all the voters who are allowed to vote for the topic
+fn get_total_eligible_weight(hash: &H256, info: &TopicInfo) -> u64 {
+    let mut total = 0;
+    if info.config.custom_voters {
+        for item in info.voters.iter() {
+            total += item.weight as u64;
+        }
+        return total;
+    }
+    if info.config.snapshot_mode != SNAPSHOT_NONE {
+        if let Some(snapshot) = get_weight_snapshot(hash) {
+            for item in snapshot.iter() {
+                total += item.weight as u64;
+            }
+            return total;
+        }
+    }
+    let peer_pool_map = get_peer_pool();
+    for item in peer_pool_map.peer_pool_map.iter() {
+        if item.status == 1 || item.status == 2 {
+            total += strategy_weight(info.config.weight_strategy, item.init_pos, item.total_pos);
+        }
+    }
+    total
+}
+
+/// the weight of a node with the given stake under the weight strategy of a topic
+fn strategy_weight(strategy: u8, init_pos: u64, total_pos: u64) -> u64 {
+    match strategy {
+        WEIGHT_ONE_NODE_ONE_VOTE => 1,
+        WEIGHT_INIT_POS => init_pos,
+        WEIGHT_SQRT_STAKE => isqrt(init_pos + total_pos),
+        _ => init_pos + total_pos,
+    }
+}
+
+/// integer square root, rounded down
+fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = n;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// the weight of the voter for the topic, taken from the voters set by the creator for custom
+/// voters topics and from the snapshot if the topic pins weights
+fn get_topic_voter_weight(hash: &H256, info: &TopicInfo, voter: &Address) -> u64 {
+    if info.config.custom_voters {
+        return get_custom_voter_weight(info, voter).unwrap_or(0);
+    }
+    if info.config.snapshot_mode == SNAPSHOT_NONE {
+        return get_voter_weight_by_strategy(voter, info.config.weight_strategy);
+    }
+    let snapshot = get_weight_snapshot(hash).unwrap_or(vec![]);
+    for item in snapshot.iter() {
+        if &item.voter == voter {
+            return item.weight as u64;
+        }
+    }
+    0
+}
+
+fn get_custom_voter_weight(info: &TopicInfo, voter: &Address) -> Option<u64> {
+    info.voters
+        .iter()
+        .find(|item| &item.voter == voter)
+        .map(|item| item.weight as u64)
+}
+
+/// set which addresses can vote for the topic and the weight of each address
+/// only the creator of a custom voters topic has the right to invoke, before start_time
+fn set_voter_for_topic(hash: &H256, voters: Vec<VoterWeight>) -> bool {
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    assert!(info.config.custom_voters);
+    assert!(check_witness(&info.gov_node_addr));
+    assert_eq!(info.status, 1);
+    assert!(timestamp() < info.start_time);
+    let mut total: u64 = 0;
+    for (i, item) in voters.iter().enumerate() {
+        assert!(item.weight > 0);
+        assert!(item.weight <= u64::MAX as U128, "voter weight overflows");
+        total = total
+            .checked_add(item.weight as u64)
+            .expect("total weight overflows");
+        assert!(voters[..i].iter().all(|other| other.voter != item.voter));
+    }
+    info.voters = voters;
+    put_topic_info(hash, info);
+    EventBuilder::new()
+        .string("setVoterForTopic")
+        .h256(hash)
+        .notify();
+    true
+}
+
 fn get_all_topic_hash_inner() -> Vec<H256> {
     let num = get_current_hash_num();
     let mut res: Vec<H256> = Vec::with_capacity(num as usize);
@@ -166,17 +428,16 @@ fn get_topic(hash: &H256) -> Option<Topic> {
 }
 
 /// cancel topic
-/// only the creator of the topic has the right to invoke
+/// only the creator of the topic and the moderators have the right to invoke
 fn cancel_topic(hash: &H256) -> bool {
     let topic_info = get_topic_info(hash);
     if let Some(mut info) = topic_info {
         assert_eq!(info.status, 1);
         let cur = timestamp();
         assert!(cur < info.end_time);
-        assert!(check_witness(&info.gov_node_addr));
+        assert!(check_witness(&info.gov_node_addr) || check_role_witness(ROLE_MODERATOR));
         info.status = 0;
-        let key = get_key(PRE_TOPIC_INFO, hash.as_ref());
-        database::put(key, info);
+        put_topic_info(hash, info);
     } else {
         panic!("the topic does not exist")
     }
@@ -187,25 +448,17 @@ fn cancel_topic(hash: &H256) -> bool {
 /// only the consensus and candidate nodes have the right to invoke
 /// approve_or_reject is true indicate approve, false indicate reject
 fn vote_topic(hash: &H256, voter: Address, approve_or_reject: bool) -> bool {
-    assert!(check_witness(&voter));
-    assert!(is_gov_node(&voter));
-    let info = get_topic_info(hash).expect("not exist topic info");
-    assert!(info.status == 1);
-    let cur = timestamp();
-    assert!(info.start_time < cur);
-    assert!(info.end_time > cur);
+    let info = check_vote(hash, &voter);
+    assert_eq!(info.config.topic_type, TOPIC_TYPE_BINARY);
     let vote_res = get_voted_info(hash, &voter);
-    if vote_res == 1 {
+    if vote_res == VOTED_APPROVE {
         assert!(approve_or_reject == false);
-    } else if vote_res == 2 {
+    } else if vote_res == VOTED_REJECT {
         assert!(approve_or_reject == true);
     }
-    let weight = get_voter_weight(&voter);
-    let vi = VotedInfo {
-        voter,
-        weight,
-        approve_or_reject,
-    };
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    vi.approve_or_reject = approve_or_reject;
     update_voted_info(hash, vi, info);
     EventBuilder::new()
         .string("voteTopic")
@@ -216,39 +469,426 @@ fn vote_topic(hash: &H256, voter: Address, approve_or_reject: bool) -> bool {
     true
 }
 
+/// vote for one option of a multiple-choice topic
+/// only the consensus and candidate nodes have the right to invoke
+/// option is the index of the chosen option in the topic options
+fn vote_topic_option(hash: &H256, voter: Address, option: U128) -> bool {
+    let info = check_vote(hash, &voter);
+    assert_eq!(info.config.topic_type, TOPIC_TYPE_MULTI_CHOICE);
+    assert!(option < info.config.options.len() as U128);
+    let option = option as u32;
+    let prev = get_all_voted_info(hash)
+        .into_iter()
+        .find(|i| i.voter == voter && !i.is_staker_vote());
+    // option of a split vote is not a choice, any option can follow it
+    if let Some(prev) = prev {
+        assert!(prev.abstain || !prev.shares.is_empty() || prev.option != option);
+    }
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    vi.option = option;
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicOption")
+        .h256(hash)
+        .address(&voter)
+        .number(option as U128)
+        .notify();
+    true
+}
+
+/// approve any subset of the options of an approval topic, every approved option gets the
+/// full weight of the voter
+/// only the consensus and candidate nodes have the right to invoke
+fn vote_topic_approval(hash: &H256, voter: Address, approvals: Vec<u32>) -> bool {
+    let info = check_vote(hash, &voter);
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    let mut ballot = Ballot::new(VOTED_APPROVAL);
+    ballot.approvals = approvals;
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicApproval")
+        .h256(hash)
+        .address(&voter)
+        .notify();
+    true
+}
+
+/// split the weight of the voter, shares are the percent of the weight for approve, reject and
+/// optionally abstain of a binary topic, or for every option of a multiple-choice topic
+/// the shares must sum to 100
+/// only the consensus and candidate nodes have the right to invoke
+fn vote_topic_split(hash: &H256, voter: Address, shares: Vec<u32>) -> bool {
+    let info = check_vote(hash, &voter);
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    let mut ballot = Ballot::new(VOTED_SPLIT);
+    ballot.shares = shares;
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicSplit")
+        .h256(hash)
+        .address(&voter)
+        .notify();
+    true
+}
+
+/// abstain from a topic, the weight counts toward turnout but not toward the outcome
+/// only the consensus and candidate nodes have the right to invoke
+fn abstain_topic(hash: &H256, voter: Address) -> bool {
+    let info = check_vote(hash, &voter);
+    assert_ne!(get_voted_info(hash, &voter), VOTED_ABSTAIN);
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    vi.abstain = true;
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("abstainTopic")
+        .h256(hash)
+        .address(&voter)
+        .notify();
+    true
+}
+
+/// cast any kind of ballot, e.g. with a conviction
+/// only the consensus and candidate nodes have the right to invoke
+fn cast_ballot(hash: &H256, voter: Address, ballot: Ballot) -> bool {
+    let info = check_vote(hash, &voter);
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("castBallot")
+        .h256(hash)
+        .address(&voter)
+        .number(ballot.choice as U128)
+        .number(ballot.conviction as U128)
+        .notify();
+    true
+}
+
+/// fill the choice of the ballot into the voted info, panics if the topic does not accept it
+fn apply_ballot(info: &TopicInfo, vi: &mut VotedInfo, ballot: &Ballot) {
+    assert!(ballot.conviction <= MAX_CONVICTION);
+    // only stake can be locked, not the weight set by the creator or derived by another strategy
+    assert!(
+        ballot.conviction == 0
+            || (info.config.weight_strategy == WEIGHT_STAKE && !info.config.custom_voters),
+        "conviction needs the stake weight strategy"
+    );
+    vi.conviction = ballot.conviction;
+    check_rationale(&ballot.rationale);
+    vi.rationale = ballot.rationale.clone();
+    match ballot.choice {
+        VOTED_APPROVE | VOTED_REJECT => {
+            assert_eq!(info.config.topic_type, TOPIC_TYPE_BINARY);
+            vi.approve_or_reject = ballot.choice == VOTED_APPROVE;
+        }
+        VOTED_OPTION => {
+            assert_eq!(info.config.topic_type, TOPIC_TYPE_MULTI_CHOICE);
+            assert!((ballot.option as usize) < info.config.options.len());
+            vi.option = ballot.option;
+        }
+        VOTED_ABSTAIN => vi.abstain = true,
+        VOTED_RANKED => {
+            assert_eq!(info.config.topic_type, TOPIC_TYPE_RANKED);
+            check_ranking(info, &ballot.ranking);
+            vi.option = ballot.ranking[0];
+            vi.ranking = ballot.ranking.clone();
+        }
+        VOTED_APPROVAL => {
+            assert_eq!(info.config.topic_type, TOPIC_TYPE_APPROVAL);
+            assert!(!ballot.approvals.is_empty());
+            for (i, option) in ballot.approvals.iter().enumerate() {
+                assert!((*option as usize) < info.config.options.len());
+                assert!(!ballot.approvals[..i].contains(option));
+            }
+            vi.approvals = ballot.approvals.clone();
+        }
+        VOTED_SPLIT => {
+            match info.config.topic_type {
+                TOPIC_TYPE_BINARY => {
+                    assert!(ballot.shares.len() == 2 || ballot.shares.len() == 3)
+                }
+                TOPIC_TYPE_MULTI_CHOICE => {
+                    assert_eq!(ballot.shares.len(), info.config.options.len())
+                }
+                _ => panic!("the topic does not accept split votes"),
+            }
+            // every share is checked first so the sum can not wrap
+            assert!(ballot.shares.iter().all(|share| *share <= 100));
+            assert_eq!(
+                ballot.shares.iter().map(|share| *share as u64).sum::<u64>(),
+                100
+            );
+            vi.option = largest_share(&ballot.shares) as u32;
+            vi.shares = ballot.shares.clone();
+        }
+        _ => panic!("unknown ballot choice"),
+    }
+}
+
+/// the text and the uri must be short, a uri must come with the hash of its content
+fn check_rationale(rationale: &Rationale) {
+    assert!(rationale.text.len() <= MAX_RATIONALE_LEN);
+    assert!(rationale.uri.len() <= MAX_RATIONALE_LEN);
+    let no_hash = rationale.content_hash == H256::repeat_byte(0);
+    assert_eq!(rationale.uri.is_empty(), no_hash);
+}
+
+/// common checks before a vote, returns the topic info
+fn check_vote(hash: &H256, voter: &Address) -> TopicInfo {
+    let info = check_voter(hash, voter);
+    assert!(!info.config.secret_ballot);
+    info
+}
+
+/// check the voter may vote or commit a ballot now, returns the topic info
+/// the first vote of a SNAPSHOT_AT_FIRST_VOTE topic also pins the voter weights
+fn check_voter(hash: &H256, voter: &Address) -> TopicInfo {
+    assert!(check_witness(voter));
+    check_node_voter(hash, voter)
+}
+
+/// check_voter without the witness, the caller must have checked the node authorized the vote
+/// the voters of a custom voters topic must be in the voters set by the creator
+fn check_node_voter(hash: &H256, voter: &Address) -> TopicInfo {
+    let info = check_topic_active(hash);
+    if info.config.custom_voters {
+        assert!(
+            get_custom_voter_weight(&info, voter).is_some(),
+            "not a voter of the topic"
+        );
+    } else {
+        assert!(is_gov_node(voter));
+    }
+    info
+}
+
+/// check the topic is in its voting period, returns the topic info
+fn check_topic_active(hash: &H256) -> TopicInfo {
+    check_not_paused(hash);
+    let info = get_topic_info(hash).expect("not exist topic info");
+    assert!(info.status == 1);
+    let cur = timestamp();
+    assert!(info.start_time < cur, "voting not started");
+    assert!(info.end_time > cur, "voting ended");
+    if info.config.snapshot_mode == SNAPSHOT_AT_FIRST_VOTE && get_weight_snapshot(hash).is_none() {
+        take_weight_snapshot(hash, info.config.weight_strategy);
+    }
+    info
+}
+
 //TODO status must be 1 or 2, other should not compute
-fn update_voted_info(hash: &H256, info: VotedInfo, mut topic_info: TopicInfo) {
+fn update_voted_info(hash: &H256, info: VotedInfo, topic_info: TopicInfo) {
+    update_vote_lock(hash, &topic_info, &info);
     let mut voted_info = get_all_voted_info(hash);
-    let mut has_voted = false;
-    let mut approve = 0;
-    let mut reject = 0;
+    match voted_info
+        .iter()
+        .position(|i| i.voter == info.voter && i.authorized_to == info.authorized_to)
+    {
+        Some(pos) => voted_info[pos] = info,
+        None => voted_info.push(info),
+    }
+    retally_topic(hash, voted_info, topic_info);
+}
+
+/// take back the vote of the node before the topic ends, the voter is not voted afterwards
+fn revoke_vote(hash: &H256, voter: Address) -> bool {
+    assert!(check_witness(&voter));
+    remove_vote(hash, &voter, &Address::new([0u8; 20]));
+    EventBuilder::new()
+        .string("revokeVote")
+        .h256(hash)
+        .address(&voter)
+        .notify();
+    true
+}
+
+/// remove the vote of the voter while the topic is active, authorized_to is the node of a
+/// staker vote or zero address for the vote of a node
+fn remove_vote(hash: &H256, voter: &Address, authorized_to: &Address) {
+    let topic_info = check_topic_active(hash);
+    let mut voted_info = get_all_voted_info(hash);
+    let pos = voted_info
+        .iter()
+        .position(|i| &i.voter == voter && &i.authorized_to == authorized_to)
+        .expect("the voter has not voted");
+    voted_info.remove(pos);
+    remove_vote_lock(hash, voter, authorized_to);
+    retally_topic(hash, voted_info, topic_info);
+}
+
+/// recompute the tally from the votes and save both
+fn retally_topic(hash: &H256, mut voted_info: Vec<VotedInfo>, mut topic_info: TopicInfo) {
+    refresh_weights(hash, &topic_info, &mut voted_info);
+    apply_delegations(hash, &topic_info, &mut voted_info);
+    tally_votes(&mut topic_info, &voted_info);
+    put_voted_info(hash, voted_info);
+    put_topic_info(hash, topic_info);
+}
+
+/// recompute the weight of every vote, the weight of the stakers who voted themselves is
+/// taken out of the weight of the node they authorized to
+fn refresh_weights(hash: &H256, info: &TopicInfo, voted_info: &mut [VotedInfo]) {
     for i in voted_info.iter_mut() {
-        if i.voter == info.voter {
-            i.approve_or_reject = info.approve_or_reject;
-            has_voted = true;
-        }
-        let weight = get_voter_weight(&i.voter);
-        i.weight = weight;
-        if i.approve_or_reject {
-            approve += i.weight;
+        i.weight = if i.is_staker_vote() {
+            get_authorized_weight(&i.authorized_to, &i.voter)
         } else {
-            reject += i.weight;
+            get_topic_voter_weight(hash, info, &i.voter)
+        };
+        enforce_vote_lock(hash, info, i);
+    }
+    subtract_staker_weights(voted_info);
+}
+
+/// take the weight of the stakers who voted themselves out of the node they authorized to
+fn subtract_staker_weights(voted_info: &mut [VotedInfo]) {
+    for pos in 0..voted_info.len() {
+        if !voted_info[pos].is_staker_vote() {
+            let staked = get_staker_voted_weight(voted_info, &voted_info[pos].voter);
+            voted_info[pos].weight = voted_info[pos].weight.saturating_sub(staked);
         }
     }
-    if !has_voted {
-        if info.approve_or_reject {
-            approve += info.weight;
+}
+
+/// seal the result of the topic after end_time, all user can invoke
+/// the tally is recomputed once and never changes afterwards
+fn finalize_topic(hash: &H256) -> bool {
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    assert_eq!(info.status, 1);
+    assert_eq!(info.tally.finalized_time, 0, "already finalized");
+    check_not_paused(hash);
+    let cur = timestamp();
+    assert!(cur >= info.voting_end_time(), "voting not ended");
+    let mut voted_info = get_voted_address(hash);
+    refresh_weights(hash, &info, &mut voted_info);
+    apply_delegations(hash, &info, &mut voted_info);
+    tally_votes(&mut info, &voted_info);
+    if info.config.topic_type == TOPIC_TYPE_RANKED {
+        info.tally.rounds = run_instant_runoff(info.config.options.len(), &voted_info);
+    }
+    let total_weight = get_total_eligible_weight(hash, &info);
+    let result = compute_result(&info, total_weight, cur);
+    info.tally.result = result;
+    info.tally.finalized_time = cur;
+    if result == RESULT_PASSED && !info.config.actions.is_empty() {
+        queue_topic(hash, &mut info);
+    }
+    put_voted_info(hash, voted_info);
+    put_topic_info(hash, info);
+    EventBuilder::new()
+        .string("topicFinalized")
+        .h256(hash)
+        .number(result as U128)
+        .number(cur as U128)
+        .notify();
+    true
+}
+
+/// recompute the weighted tally of the topic from all the votes
+fn tally_votes(topic_info: &mut TopicInfo, voted_info: &[VotedInfo]) {
+    let mut approve = 0;
+    let mut reject = 0;
+    let mut abstain = 0;
+    let mut options = vec![0u64; topic_info.config.options.len()];
+    let (mut approve_count, mut reject_count, mut abstain_count) = (0, 0, 0);
+    let mut option_counts = vec![0u32; topic_info.config.options.len()];
+    let (mut voted, mut voted_count) = (0, 0);
+    let mut turnout = 0;
+    let binary = topic_info.config.topic_type == TOPIC_TYPE_BINARY;
+    for i in voted_info.iter() {
+        let weight = i.total_weight();
+        let count = i.node_count();
+        turnout += i.turnout_weight();
+        if i.abstain {
+            abstain += weight;
+            abstain_count += count;
+            continue;
+        }
+        if !i.shares.is_empty() {
+            // the nodes of a split vote count for the bucket with the largest share
+            let parts = split_weight(weight, &i.shares);
+            let major = largest_share(&i.shares);
+            if binary {
+                let abstain_part = parts.get(2).cloned().unwrap_or(0);
+                approve += parts[0];
+                reject += parts[1];
+                abstain += abstain_part;
+                voted += weight - abstain_part;
+                match major {
+                    0 => approve_count += count,
+                    1 => reject_count += count,
+                    _ => abstain_count += count,
+                }
+                if major < 2 {
+                    voted_count += count;
+                }
+            } else {
+                for (option, part) in parts.iter().enumerate() {
+                    options[option] += part;
+                }
+                option_counts[major] += count;
+                voted += weight;
+                voted_count += count;
+            }
+            continue;
+        }
+        voted += weight;
+        voted_count += count;
+        if topic_info.config.topic_type == TOPIC_TYPE_APPROVAL {
+            for option in i.approvals.iter() {
+                options[*option as usize] += weight;
+                option_counts[*option as usize] += count;
+            }
+        } else if topic_info.config.topic_type != TOPIC_TYPE_BINARY {
+            options[i.option as usize] += weight;
+            option_counts[i.option as usize] += count;
+        } else if i.approve_or_reject {
+            approve += weight;
+            approve_count += count;
         } else {
-            reject += info.weight;
+            reject += weight;
+            reject_count += count;
         }
-        voted_info.push(info);
     }
-    let key = get_key(PRE_VOTED, hash.as_ref());
-    database::put(key, voted_info);
-    topic_info.reject = reject;
     topic_info.approve = approve;
-    let key = get_key(PRE_TOPIC_INFO, hash.as_ref());
-    database::put(key.as_slice(), topic_info);
+    topic_info.reject = reject;
+    topic_info.tally.options = options;
+    topic_info.tally.abstain = abstain;
+    topic_info.tally.approve_count = approve_count;
+    topic_info.tally.reject_count = reject_count;
+    topic_info.tally.abstain_count = abstain_count;
+    topic_info.tally.option_counts = option_counts;
+    topic_info.tally.voted = voted;
+    topic_info.tally.voted_count = voted_count;
+    topic_info.tally.turnout = turnout;
+}
+
+/// split the weight by the percent shares, the remainder of the division goes to the largest share
+fn split_weight(weight: u64, shares: &[u32]) -> Vec<u64> {
+    let mut parts: Vec<u64> = shares
+        .iter()
+        .map(|share| (weight as U128 * *share as U128 / 100) as u64)
+        .collect();
+    let rest = weight - parts.iter().sum::<u64>();
+    parts[largest_share(shares)] += rest;
+    parts
+}
+
+/// index of the largest share, the first one if several shares are the largest
+fn largest_share(shares: &[u32]) -> usize {
+    let mut largest = 0;
+    for (i, share) in shares.iter().enumerate() {
+        if *share > shares[largest] {
+            largest = i;
+        }
+    }
+    largest
 }
 
 /// ****all user can invoke method ***********
@@ -269,19 +909,28 @@ fn list_topic_hash() -> Vec<H256> {
 }
 
 fn get_voter_weight(voter: &Address) -> u64 {
+    get_voter_weight_by_strategy(voter, WEIGHT_STAKE)
+}
+
+fn get_voter_weight_by_strategy(voter: &Address, strategy: u8) -> u64 {
     let item = governance::get_peer_info(voter);
     if item.status != 1 && item.status != 2 {
         return 0;
     }
     if &item.peer_pubkey_addr != &Address::new([0u8; 20]) && &item.peer_pubkey_addr == voter {
-        return item.init_pos + item.total_pos;
+        return strategy_weight(strategy, item.init_pos, item.total_pos);
     }
     0
 }
 
+/// the weight of the voter for the topic, under the weight strategy of the topic
+fn get_topic_voter_weight_by_hash(hash: &H256, voter: &Address) -> u64 {
+    let info = get_topic_info(hash).expect("not exist topic info");
+    get_topic_voter_weight(hash, &info, voter)
+}
+
 fn get_voted_address(hash: &H256) -> Vec<VotedInfo> {
-    let voted_info = get_all_voted_info(hash);
-    if voted_info.len() != 0 {
+    if let Some(voted_info) = get_stored_voted_info(hash) {
         return voted_info;
     }
     let res = neo::call_contract(
@@ -318,18 +967,40 @@ fn get_topic_info_list_by_addr(gov_node_addr: &Address) -> Vec<TopicInfo> {
     res
 }
 
-/// 1: approve, 2: reject, other: not voted
+/// 1: approve, 2: reject, 3: voted for an option of a multiple-choice topic, 4: abstain,
+/// 5: voted for a ranked topic, 6: voted for an approval topic, 7: split the weight,
+/// other: not voted
 fn get_voted_info(hash: &H256, voter: &Address) -> u8 {
-    let voted_info = get_all_voted_info(hash);
+    let voted_info = match get_stored_voted_info(hash) {
+        Some(voted_info) => voted_info,
+        None => return get_neo_voted_info(hash, voter),
+    };
     for info in voted_info.iter() {
         if &info.voter == voter {
-            if info.approve_or_reject {
-                return 1;
+            if info.abstain {
+                return VOTED_ABSTAIN;
+            }
+            if !info.shares.is_empty() {
+                return VOTED_SPLIT;
+            }
+            let topic_type = get_topic_type(hash);
+            if topic_type == TOPIC_TYPE_MULTI_CHOICE {
+                return VOTED_OPTION;
+            } else if topic_type == TOPIC_TYPE_RANKED {
+                return VOTED_RANKED;
+            } else if topic_type == TOPIC_TYPE_APPROVAL {
+                return VOTED_APPROVAL;
+            } else if info.approve_or_reject {
+                return VOTED_APPROVE;
             } else {
-                return 2;
+                return VOTED_REJECT;
             }
         }
     }
+    VOTED_NONE
+}
+
+fn get_neo_voted_info(hash: &H256, voter: &Address) -> u8 {
     let res = neo::call_contract(
         &NEO_VOTE_CONTRACT_ADDRESS,
         (
@@ -342,12 +1013,53 @@ fn get_voted_info(hash: &H256, voter: &Address) -> u8 {
         let r = parser.number().unwrap_or_default();
         return r as u8;
     }
-    0
+    VOTED_NONE
+}
+
+/// the options the voter chose: the option of a multiple-choice topic, the ranking of a ranked
+/// topic or the approved options of an approval topic, empty if not voted or abstained
+fn get_voted_options(hash: &H256, voter: &Address) -> Vec<u32> {
+    let voted_info = get_all_voted_info(hash);
+    let info = match voted_info.into_iter().find(|i| &i.voter == voter) {
+        Some(info) => info,
+        None => return vec![],
+    };
+    if info.abstain {
+        return vec![];
+    }
+    match get_topic_type(hash) {
+        TOPIC_TYPE_MULTI_CHOICE => vec![info.option],
+        TOPIC_TYPE_RANKED => info.ranking,
+        TOPIC_TYPE_APPROVAL => info.approvals,
+        _ => vec![],
+    }
+}
+
+fn get_topic_type(hash: &H256) -> u8 {
+    get_topic_info(hash)
+        .map(|info| info.config.topic_type)
+        .unwrap_or(TOPIC_TYPE_BINARY)
+}
+
+fn put_voted_info(hash: &H256, voted_info: Vec<VotedInfo>) {
+    let key = get_key(PRE_VOTED_V2, hash.as_ref());
+    database::put(key, voted_info);
 }
 
 fn get_all_voted_info(hash: &H256) -> Vec<VotedInfo> {
+    get_stored_voted_info(hash).unwrap_or(vec![])
+}
+
+/// the votes stored by this contract, none if the votes of the topic are only in the NEO vote
+/// contract, an empty list means every vote was revoked
+fn get_stored_voted_info(hash: &H256) -> Option<Vec<VotedInfo>> {
+    let key = get_key(PRE_VOTED_V2, hash.as_ref());
+    if let Some(voted_info) = database::get::<_, Vec<VotedInfo>>(key) {
+        return Some(voted_info);
+    }
     let key = get_key(PRE_VOTED, hash.as_ref());
-    database::get::<_, Vec<VotedInfo>>(key).unwrap_or(vec![])
+    let legacy = database::get::<_, Vec<LegacyVotedInfo>>(key)?;
+    Some(legacy.into_iter().map(VotedInfo::from).collect())
 }
 
 fn is_gov_node(gov_node_addr: &Address) -> bool {
@@ -358,11 +1070,27 @@ fn is_gov_node(gov_node_addr: &Address) -> bool {
     true
 }
 
+fn put_topic_info(hash: &H256, info: TopicInfo) {
+    let key = get_key(PRE_TOPIC_INFO_V2, hash.as_ref());
+    database::put(key, info);
+}
+
+/// the topic info with the contract pauses given back to its voting
 fn get_topic_info(hash: &H256) -> Option<TopicInfo> {
+    let mut info = get_stored_topic_info(hash)?;
+    fold_pause_periods(hash, &mut info);
+    Some(info)
+}
+
+fn get_stored_topic_info(hash: &H256) -> Option<TopicInfo> {
+    let key = get_key(PRE_TOPIC_INFO_V2, hash.as_ref());
+    if let Some(info) = database::get::<_, TopicInfo>(key) {
+        return Some(info);
+    }
     let key = get_key(PRE_TOPIC_INFO, hash.as_ref());
-    let info = database::get::<_, TopicInfo>(key);
+    let info = database::get::<_, LegacyTopicInfo>(key);
     if let Some(temp) = info {
-        return Some(temp);
+        return Some(temp.into());
     } else {
         let res = neo::call_contract(
             &NEO_VOTE_CONTRACT_ADDRESS,
@@ -392,17 +1120,70 @@ pub fn invoke() {
         b"getAdmin" => {
             sink.write(get_admin());
         }
-        b"updateAdmin" => {
-            let addr = source.read().unwrap();
-            sink.write(update_admin(addr));
+        b"getAdmins" => {
+            sink.write(get_admins());
+        }
+        b"hasRole" => {
+            let (role, addr) = source.read().unwrap();
+            sink.write(has_role(role, addr));
+        }
+        b"getRoleMembers" => {
+            let role = source.read().unwrap();
+            sink.write(get_role_members(role));
+        }
+        b"pause" => {
+            sink.write(set_paused(true));
+        }
+        b"unpause" => {
+            sink.write(set_paused(false));
         }
-        b"migrate" => {
-            let (code, vm_ty, name, version, author, email, desc) = source.read().unwrap();
-            sink.write(migrate(code, vm_ty, name, version, author, email, desc))
+        b"isPaused" => {
+            sink.write(is_paused());
+        }
+        b"getPausedSince" => {
+            sink.write(get_paused_since());
+        }
+        b"pauseTopic" => {
+            let hash = source.read().unwrap();
+            sink.write(pause_topic(hash));
+        }
+        b"unpauseTopic" => {
+            let hash = source.read().unwrap();
+            sink.write(unpause_topic(hash));
+        }
+        b"getTopicPausedSince" => {
+            let hash = source.read().unwrap();
+            sink.write(get_topic_paused_since(hash));
+        }
+        b"proposeAdminAction" => {
+            let (proposer, method, args, expiry) = source.read().unwrap();
+            sink.write(propose_admin_action(proposer, method, args, expiry));
+        }
+        b"confirmAdminAction" => {
+            let (id, admin) = source.read().unwrap();
+            sink.write(confirm_admin_action(id, admin));
+        }
+        b"acceptAdmin" => {
+            let new_admin = source.read().unwrap();
+            sink.write(accept_admin(new_admin));
+        }
+        b"getPendingAdmin" => {
+            sink.write(get_pending_admin());
+        }
+        b"getAdminProposal" => {
+            let id = source.read().unwrap();
+            sink.write(get_admin_proposal(id));
+        }
+        b"getGuardians" => {
+            sink.write(get_guardians());
         }
         b"listGovNodes" => {
             sink.write(list_gov_nodes());
         }
+        b"setVoterForTopic" => {
+            let (hash, voters) = source.read().unwrap();
+            sink.write(set_voter_for_topic(hash, voters));
+        }
         b"listTopics" => {
             sink.write(list_topic_hash());
         }
@@ -411,6 +1192,10 @@ pub fn invoke() {
             sink.write(get_topic(hash));
         }
         b"getTopicInfo" => {
+            let hash = source.read().expect("parameter should be H256");
+            sink.write(get_topic_info(hash).map(LegacyTopicInfo::from));
+        }
+        b"getTopicInfoV2" => {
             let hash = source.read().expect("parameter should be H256");
             sink.write(get_topic_info(hash));
         }
@@ -427,6 +1212,18 @@ pub fn invoke() {
                 end_time,
             ));
         }
+        b"createTopicWithConfig" => {
+            let (admin, topic_title, topic_detail, start_time, end_time, config) =
+                source.read().unwrap();
+            sink.write(create_topic_with_config(
+                admin,
+                topic_title,
+                topic_detail,
+                start_time,
+                end_time,
+                config,
+            ));
+        }
         b"cancelTopic" => {
             let hash = source.read().unwrap();
             sink.write(cancel_topic(hash));
@@ -435,19 +1232,202 @@ pub fn invoke() {
             let (hash, voter, approve_or_reject) = source.read().unwrap();
             sink.write(vote_topic(hash, voter, approve_or_reject));
         }
+        b"voteTopicOption" => {
+            let (hash, voter, option) = source.read().unwrap();
+            sink.write(vote_topic_option(hash, voter, option));
+        }
+        b"voteTopicRanked" => {
+            let (hash, voter, ranking) = source.read().unwrap();
+            sink.write(vote_topic_ranked(hash, voter, ranking));
+        }
+        b"voteTopicApproval" => {
+            let (hash, voter, approvals) = source.read().unwrap();
+            sink.write(vote_topic_approval(hash, voter, approvals));
+        }
+        b"voteTopicSplit" => {
+            let (hash, voter, shares) = source.read().unwrap();
+            sink.write(vote_topic_split(hash, voter, shares));
+        }
+        b"revokeVote" => {
+            let (hash, voter) = source.read().unwrap();
+            sink.write(revoke_vote(hash, voter));
+        }
+        b"castBallot" => {
+            let (hash, voter, ballot) = source.read().unwrap();
+            sink.write(cast_ballot(hash, voter, ballot));
+        }
+        b"unlockVote" => {
+            let (hash, voter, authorized_to) = source.read().unwrap();
+            sink.write(unlock_vote(hash, voter, authorized_to));
+        }
+        b"checkVoteLock" => {
+            let (hash, voter, authorized_to) = source.read().unwrap();
+            sink.write(check_vote_lock(hash, voter, authorized_to));
+        }
+        b"getVoteLock" => {
+            let (hash, voter, authorized_to) = source.read().unwrap();
+            sink.write(get_vote_lock(hash, voter, authorized_to));
+        }
+        b"getVoteLocks" => {
+            let voter = source.read().unwrap();
+            sink.write(get_vote_locks(voter));
+        }
+        b"abstainTopic" => {
+            let (hash, voter) = source.read().unwrap();
+            sink.write(abstain_topic(hash, voter));
+        }
+        b"commitVote" => {
+            let (hash, voter, commitment) = source.read().unwrap();
+            sink.write(commit_vote(hash, voter, commitment));
+        }
+        b"revealVote" => {
+            let (hash, voter, ballot, salt) = source.read().unwrap();
+            sink.write(reveal_vote(hash, voter, ballot, salt));
+        }
+        b"getCommitments" => {
+            let hash = source.read().unwrap();
+            sink.write(get_commitments(hash));
+        }
+        b"getUnrevealedCommitments" => {
+            let hash = source.read().unwrap();
+            sink.write(get_unrevealed_commitments(hash));
+        }
+        b"delegateVote" => {
+            let (delegator, delegate) = source.read().unwrap();
+            sink.write(delegate_vote(delegator, delegate));
+        }
+        b"revokeDelegation" => {
+            let delegator = source.read().unwrap();
+            sink.write(revoke_delegation(delegator));
+        }
+        b"delegateTopicVote" => {
+            let (hash, delegator, delegate) = source.read().unwrap();
+            sink.write(delegate_topic_vote(hash, delegator, delegate));
+        }
+        b"revokeTopicDelegation" => {
+            let (hash, delegator) = source.read().unwrap();
+            sink.write(revoke_topic_delegation(hash, delegator));
+        }
+        b"getDelegations" => {
+            sink.write(get_delegations());
+        }
+        b"getTopicDelegations" => {
+            let hash = source.read().unwrap();
+            sink.write(get_topic_delegations(hash));
+        }
+        b"voteTopicAsStaker" => {
+            let (hash, staker, node, ballot) = source.read().unwrap();
+            sink.write(vote_topic_as_staker(hash, staker, node, ballot));
+        }
+        b"revokeStakerVote" => {
+            let (hash, staker, node) = source.read().unwrap();
+            sink.write(revoke_staker_vote(hash, staker, node));
+        }
+        b"getAuthorizedWeight" => {
+            let (node, staker) = source.read().unwrap();
+            sink.write(get_authorized_weight(node, staker));
+        }
+        b"bindOntId" => {
+            let (ont_id, index, node) = source.read().unwrap();
+            sink.write(bind_ont_id(ont_id, index, node));
+        }
+        b"getOntIdNode" => {
+            let ont_id = source.read().unwrap();
+            sink.write(get_ont_id_node(ont_id));
+        }
+        b"createTopicByOntId" => {
+            let (ont_id, index, topic_title, topic_detail, start_time, end_time, config) =
+                source.read().unwrap();
+            sink.write(create_topic_by_ont_id(
+                ont_id,
+                index,
+                topic_title,
+                topic_detail,
+                start_time,
+                end_time,
+                config,
+            ));
+        }
+        b"voteTopicByOntId" => {
+            let (hash, ont_id, index, ballot) = source.read().unwrap();
+            sink.write(vote_topic_by_ont_id(hash, ont_id, index, ballot));
+        }
         b"getVoterWeight" => {
             let voter = source.read().unwrap();
             sink.write(get_voter_weight(voter));
         }
+        b"getTopicVoterWeight" => {
+            let (hash, voter) = source.read().unwrap();
+            sink.write(get_topic_voter_weight_by_hash(hash, voter));
+        }
+        b"getWeightSnapshot" => {
+            let hash = source.read().unwrap();
+            sink.write(get_weight_snapshot(hash).unwrap_or(vec![]));
+        }
+        b"executeTopic" => {
+            let hash = source.read().unwrap();
+            sink.write(execute_topic(hash));
+        }
+        b"cancelQueuedTopic" => {
+            let (hash, guardian) = source.read().unwrap();
+            sink.write(cancel_queued_topic(hash, guardian));
+        }
+        b"vetoTopic" => {
+            let (hash, guardian, reason) = source.read().unwrap();
+            sink.write(veto_topic(hash, guardian, reason));
+        }
+        b"getVetoPeriod" => {
+            sink.write(get_veto_period());
+        }
+        b"getTopicVeto" => {
+            let hash = source.read().unwrap();
+            sink.write(get_topic_veto(hash));
+        }
+        b"getQueuedTopics" => {
+            sink.write(get_queued_topics());
+        }
+        b"getTimelockDelay" => {
+            sink.write(get_timelock_delay());
+        }
+        b"finalizeTopic" => {
+            let hash = source.read().unwrap();
+            sink.write(finalize_topic(hash));
+        }
+        b"getTopicResult" => {
+            let hash = source.read().unwrap();
+            sink.write(get_topic_result(hash));
+        }
         b"getVotedInfo" => {
             let (hash, voter) = source.read().unwrap();
             sink.write(get_voted_info(hash, voter));
         }
+        b"getVotedOptions" => {
+            let (hash, voter) = source.read().unwrap();
+            sink.write(get_voted_options(hash, voter));
+        }
         b"getVotedAddress" => {
+            let hash = source.read().unwrap();
+            let voted_info = get_voted_address(hash);
+            // the old layout only while it loses nothing, otherwise the layout of getVotedAddressV2
+            let binary = get_topic_type(hash) == TOPIC_TYPE_BINARY;
+            if binary && voted_info.iter().all(VotedInfo::is_legacy) {
+                let legacy: Vec<LegacyVotedInfo> = voted_info.into_iter().map(From::from).collect();
+                sink.write(legacy);
+            } else {
+                sink.write(voted_info);
+            }
+        }
+        b"getVotedAddressV2" => {
             let hash = source.read().unwrap();
             sink.write(get_voted_address(hash));
         }
         b"getTopicInfoListByAddr" => {
+            let admin = source.read().unwrap();
+            let topic_info = get_topic_info_list_by_addr(admin);
+            let legacy: Vec<LegacyTopicInfo> = topic_info.into_iter().map(From::from).collect();
+            sink.write(legacy);
+        }
+        b"getTopicInfoListByAddrV2" => {
             let admin = source.read().unwrap();
             sink.write(get_topic_info_list_by_addr(admin));
         }
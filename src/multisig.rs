@@ -0,0 +1,268 @@
+use super::*;
+
+/// the privileged actions which need the confirmations of the admins
+pub const ADMIN_METHODS: [&[u8]; 11] = [
+    b"setAdmins",
+    b"migrate",
+    b"proposeAdmin",
+    b"cancelAdminTransfer",
+    b"grantRole",
+    b"revokeRole",
+    b"setTimelockDelay",
+    b"setVetoPeriod",
+    b"addGuardian",
+    b"removeGuardian",
+    b"cancelQueuedTopic",
+];
+/// an admin proposal expires at most 30 days after it is proposed
+pub const MAX_ADMIN_PROPOSAL_LIFETIME: u64 = 30 * 24 * 3600;
+
+/// the admin set, before any setAdmins it is the single admin of the old contract
+pub fn get_admins() -> AdminSet {
+    if let Some(admins) = database::get::<_, AdminSet>(KEY_ADMINS) {
+        return admins;
+    }
+    let admin = database::get::<_, Address>(KEY_ADMIN).unwrap_or(ADMIN);
+    AdminSet {
+        admins: vec![admin],
+        threshold: 1,
+    }
+}
+
+/// the first admin of the set, for the callers of getAdmin from before the admin set
+pub fn get_admin() -> Address {
+    get_admins().admins[0]
+}
+
+pub fn is_admin(addr: &Address) -> bool {
+    get_admins().admins.contains(addr)
+}
+
+/// the admins who can propose and confirm the method, migrate also needs ROLE_UPGRADER
+fn can_confirm(admins: &AdminSet, method: &[u8], addr: &Address) -> bool {
+    admins.admins.contains(addr) && (method != b"migrate" || has_role(ROLE_UPGRADER, addr))
+}
+
+/// remove admins or change the threshold, only an admin proposal can invoke
+/// new admins must accept with acceptAdmin, see propose_admin
+pub fn set_admins(admins: Vec<Address>, threshold: u32) -> bool {
+    check_admin_set(&admins, threshold);
+    let cur = get_admins();
+    assert!(admins.iter().all(|a| cur.admins.contains(a)));
+    database::put(KEY_ADMINS, AdminSet { admins, threshold });
+    EventBuilder::new()
+        .string("setAdmins")
+        .number(threshold as U128)
+        .notify();
+    true
+}
+
+/// start the transfer of an admin seat to new_admin, only an admin proposal can invoke
+/// replaced is the admin leaving the set, or zero address to add a seat
+/// a pending transfer must be accepted or canceled before another one is proposed
+pub fn propose_admin(new_admin: Address, replaced: Address) -> bool {
+    assert!(
+        get_pending_admin().is_none(),
+        "an admin transfer is pending"
+    );
+    assert_ne!(new_admin, Address::new([0u8; 20]));
+    assert!(!is_admin(&new_admin));
+    if replaced != Address::new([0u8; 20]) {
+        assert!(is_admin(&replaced));
+    }
+    database::put(
+        KEY_PENDING_ADMIN,
+        PendingAdmin {
+            new_admin,
+            replaced,
+        },
+    );
+    EventBuilder::new()
+        .string("proposeAdmin")
+        .address(&new_admin)
+        .address(&replaced)
+        .notify();
+    true
+}
+
+/// the new admin takes the seat proposed to it, only the new admin can invoke
+pub fn accept_admin(new_admin: Address) -> bool {
+    assert!(check_witness(&new_admin));
+    let pending = get_pending_admin().expect("no pending admin");
+    assert_eq!(pending.new_admin, new_admin);
+    let mut set = get_admins();
+    if pending.replaced != Address::new([0u8; 20]) {
+        // setAdmins may have removed the replaced admin since the transfer was proposed
+        assert!(set.admins.contains(&pending.replaced));
+        set.admins.retain(|a| a != &pending.replaced);
+    }
+    set.admins.push(new_admin);
+    check_admin_set(&set.admins, set.threshold);
+    database::put(KEY_ADMINS, set);
+    database::delete(KEY_PENDING_ADMIN);
+    EventBuilder::new()
+        .string("acceptAdmin")
+        .address(&new_admin)
+        .address(&pending.replaced)
+        .notify();
+    true
+}
+
+/// drop the pending admin transfer, only an admin proposal can invoke
+pub fn cancel_admin_transfer() -> bool {
+    let pending = get_pending_admin().expect("no pending admin");
+    database::delete(KEY_PENDING_ADMIN);
+    EventBuilder::new()
+        .string("cancelAdminTransfer")
+        .address(&pending.new_admin)
+        .notify();
+    true
+}
+
+pub fn get_pending_admin() -> Option<PendingAdmin> {
+    database::get::<_, PendingAdmin>(KEY_PENDING_ADMIN)
+}
+
+pub fn check_admin_set(admins: &[Address], threshold: u32) {
+    assert!(threshold >= 1);
+    assert!(threshold as usize <= admins.len());
+    for (i, admin) in admins.iter().enumerate() {
+        assert!(!admins[..i].contains(admin));
+    }
+}
+
+/// propose a privileged action, only the admins can invoke
+/// the proposal counts as the confirmation of the proposer, it runs at once for a 1-of-N set
+pub fn propose_admin_action(proposer: Address, method: &[u8], args: &[u8], expiry: u64) -> bool {
+    assert!(ADMIN_METHODS.iter().any(|m| *m == method));
+    assert!(
+        can_confirm(&get_admins(), method, &proposer),
+        "not an admin who can confirm the method"
+    );
+    assert!(check_witness(&proposer));
+    let cur = timestamp();
+    assert!(expiry > cur && expiry <= cur + MAX_ADMIN_PROPOSAL_LIFETIME);
+    let id = database::get::<_, u64>(KEY_ADMIN_PROPOSAL_NUM).unwrap_or(0);
+    database::put(KEY_ADMIN_PROPOSAL_NUM, id + 1);
+    let proposal = AdminProposal {
+        id,
+        proposer,
+        method: method.to_vec(),
+        args: args.to_vec(),
+        confirmations: vec![proposer],
+        expiry,
+        executed: false,
+    };
+    EventBuilder::new()
+        .string("proposeAdminAction")
+        .number(id as U128)
+        .address(&proposer)
+        .bytearray(method)
+        .number(expiry as U128)
+        .notify();
+    try_execute_admin_proposal(proposal);
+    true
+}
+
+/// confirm the proposal of another admin, only the admins can invoke
+pub fn confirm_admin_action(id: u64, admin: Address) -> bool {
+    let mut proposal = get_admin_proposal(id).expect("not exist admin proposal");
+    assert!(
+        can_confirm(&get_admins(), &proposal.method, &admin),
+        "not an admin who can confirm the method"
+    );
+    assert!(check_witness(&admin));
+    assert!(!proposal.executed);
+    assert!(timestamp() < proposal.expiry);
+    assert!(!proposal.confirmations.contains(&admin));
+    proposal.confirmations.push(admin);
+    EventBuilder::new()
+        .string("confirmAdminAction")
+        .number(id as U128)
+        .address(&admin)
+        .notify();
+    try_execute_admin_proposal(proposal);
+    true
+}
+
+pub fn get_admin_proposal(id: u64) -> Option<AdminProposal> {
+    database::get::<_, AdminProposal>(get_key(PRE_ADMIN_PROPOSAL, id.to_string().as_bytes()))
+}
+
+fn put_admin_proposal(proposal: &AdminProposal) {
+    let key = get_key(PRE_ADMIN_PROPOSAL, proposal.id.to_string().as_bytes());
+    database::put(key, proposal);
+}
+
+/// run the proposal once the admins who confirmed it reach the threshold of the current set,
+/// confirmations of removed admins, or of admins who lost ROLE_UPGRADER for migrate, do not
+/// count
+fn try_execute_admin_proposal(mut proposal: AdminProposal) {
+    let admins = get_admins();
+    let confirmed = proposal
+        .confirmations
+        .iter()
+        .filter(|a| can_confirm(&admins, &proposal.method, a))
+        .count();
+    if confirmed < admins.threshold as usize {
+        put_admin_proposal(&proposal);
+        return;
+    }
+    proposal.executed = true;
+    put_admin_proposal(&proposal);
+    run_admin_action(&proposal.method, &proposal.args);
+    EventBuilder::new()
+        .string("executeAdminAction")
+        .number(proposal.id as U128)
+        .notify();
+}
+
+fn run_admin_action(method: &[u8], args: &[u8]) {
+    let mut source = Source::new(args);
+    match method {
+        b"setAdmins" => {
+            let (admins, threshold) = source.read().unwrap();
+            set_admins(admins, threshold);
+        }
+        b"proposeAdmin" => {
+            let (new_admin, replaced) = source.read().unwrap();
+            propose_admin(new_admin, replaced);
+        }
+        b"cancelAdminTransfer" => {
+            cancel_admin_transfer();
+        }
+        b"migrate" => {
+            let (code, vm_ty, name, version, author, email, desc) = source.read().unwrap();
+            migrate(code, vm_ty, name, version, author, email, desc);
+        }
+        b"grantRole" => {
+            let (role, addr) = source.read().unwrap();
+            grant_role(role, addr);
+        }
+        b"revokeRole" => {
+            let (role, addr) = source.read().unwrap();
+            revoke_role(role, addr);
+        }
+        b"setTimelockDelay" => {
+            let delay = source.read().unwrap();
+            set_timelock_delay(delay);
+        }
+        b"setVetoPeriod" => {
+            let period = source.read().unwrap();
+            set_veto_period(period);
+        }
+        b"addGuardian" => {
+            let addr = source.read().unwrap();
+            add_guardian(addr);
+        }
+        b"removeGuardian" => {
+            let addr = source.read().unwrap();
+            remove_guardian(addr);
+        }
+        b"cancelQueuedTopic" => {
+            let hash = source.read().unwrap();
+            cancel_queued(hash);
+        }
+        _ => panic!("unknown admin method"),
+    }
+}
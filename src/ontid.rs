@@ -0,0 +1,89 @@
+use super::*;
+use ostd::runtime::call_contract;
+
+/// bind the ONT ID to the gov node, afterwards the ONT ID can create topics and vote for the node
+/// both the ONT ID and the gov node must sign the transaction, binding again replaces the node
+pub fn bind_ont_id(ont_id: &[u8], index: U128, node: Address) -> bool {
+    assert!(check_witness(&node));
+    assert!(is_gov_node(&node));
+    assert!(verify_ont_id(ont_id, index));
+    database::put(get_key(PRE_ONT_ID_NODE, ont_id), &node);
+    EventBuilder::new()
+        .string("bindOntId")
+        .bytearray(ont_id)
+        .address(&node)
+        .notify();
+    true
+}
+
+pub fn get_ont_id_node(ont_id: &[u8]) -> Option<Address> {
+    database::get::<_, Address>(get_key(PRE_ONT_ID_NODE, ont_id))
+}
+
+/// create topic with the ONT ID bound to a gov node
+pub fn create_topic_by_ont_id(
+    ont_id: &[u8],
+    index: U128,
+    topic_title: &[u8],
+    topic_detail: &[u8],
+    start_time: U128,
+    end_time: U128,
+    config: TopicConfig,
+) -> bool {
+    let node = check_ont_id(ont_id, index);
+    create_topic_inner(
+        node,
+        topic_title,
+        topic_detail,
+        start_time,
+        end_time,
+        config,
+    )
+}
+
+/// vote for the gov node bound to the ONT ID, the VotedInfo records the ONT ID
+pub fn vote_topic_by_ont_id(hash: &H256, ont_id: &[u8], index: U128, ballot: Ballot) -> bool {
+    let node = check_ont_id(ont_id, index);
+    let info = check_node_voter(hash, &node);
+    assert!(!info.config.secret_ballot);
+    let weight = get_topic_voter_weight(hash, &info, &node);
+    let mut vi = VotedInfo::new(node, weight);
+    vi.ont_id = ont_id.to_vec();
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicByOntId")
+        .h256(hash)
+        .bytearray(ont_id)
+        .address(&node)
+        .number(ballot.choice as U128)
+        .number(ballot.option as U128)
+        .notify();
+    true
+}
+
+/// check the caller controls the ONT ID, returns the gov node bound to it
+fn check_ont_id(ont_id: &[u8], index: U128) -> Address {
+    assert!(verify_ont_id(ont_id, index));
+    get_ont_id_node(ont_id).expect("the ONT ID is not bound to a gov node")
+}
+
+fn ont_id_contract_address() -> Address {
+    let mut addr = [0u8; 20];
+    addr[19] = 3;
+    Address::new(addr)
+}
+
+/// invoke verifySignature of the ONT ID native contract, true if the transaction is signed by
+/// the public key at index of the ONT ID
+fn verify_ont_id(ont_id: &[u8], index: U128) -> bool {
+    let mut args = Sink::new(64);
+    args.write(ont_id);
+    args.write_native_varuint(index as u64);
+    let mut sink = Sink::new(64);
+    sink.write(0u8);
+    sink.write("verifySignature");
+    sink.write(args.bytes());
+    let res = call_contract(&ont_id_contract_address(), sink.bytes());
+    res.map(|r| r == [1u8]).unwrap_or(false)
+}
@@ -0,0 +1,124 @@
+use super::*;
+
+/// when the whole contract was paused, 0 means the contract is not paused
+pub fn get_paused_since() -> u64 {
+    database::get::<_, u64>(KEY_PAUSED).unwrap_or(0)
+}
+
+/// the whole contract is paused, topics can not be created, voted, finalized or executed
+pub fn is_paused() -> bool {
+    get_paused_since() != 0
+}
+
+/// halt or resume topic creation and voting, only the pausers have the right to invoke
+/// the queries keep working during the pause, on resume the pause is recorded and given
+/// back to the topics when they are read, see fold_pause_periods
+pub fn set_paused(paused: bool) -> bool {
+    assert!(check_role_witness(ROLE_PAUSER));
+    assert_ne!(is_paused(), paused);
+    let cur = timestamp();
+    if paused {
+        database::put(KEY_PAUSED, cur);
+    } else {
+        let mut periods = get_pause_periods();
+        periods.push(PausePeriod {
+            start: get_paused_since(),
+            end: cur,
+        });
+        database::put(KEY_PAUSE_PERIODS, periods);
+        database::delete(KEY_PAUSED);
+    }
+    EventBuilder::new()
+        .string(if paused { "pause" } else { "unpause" })
+        .number(cur as U128)
+        .notify();
+    true
+}
+
+/// the ended pauses of the whole contract, oldest first
+pub fn get_pause_periods() -> Vec<PausePeriod> {
+    database::get::<_, Vec<PausePeriod>>(KEY_PAUSE_PERIODS).unwrap_or(vec![])
+}
+
+/// give the contract pauses which ended since the topic was stored back to its voting
+/// only the part of a pause after the voting started counts, and only if the voting had not
+/// ended when that part began; the pauses inside a pause of the topic itself are given back
+/// by unpauseTopic
+pub fn fold_pause_periods(hash: &H256, info: &mut TopicInfo) {
+    let periods = get_pause_periods();
+    if periods.len() <= info.pause_periods as usize {
+        return;
+    }
+    let topic_paused_since = get_topic_paused_since(hash);
+    for p in periods.iter().skip(info.pause_periods as usize) {
+        if topic_paused_since != 0 && p.start >= topic_paused_since {
+            continue;
+        }
+        let start = p.start.max(info.start_time);
+        if info.status == 1 && start < info.voting_end_time() {
+            extend_voting(info, p.end.saturating_sub(start));
+        }
+    }
+    info.pause_periods = periods.len() as u32;
+}
+
+/// push end_time, and reveal_end_time of a secret topic, back by the paused duration
+pub fn extend_voting(info: &mut TopicInfo, paused: u64) {
+    info.end_time += paused;
+    if info.config.secret_ballot {
+        info.config.reveal_end_time += paused;
+    }
+}
+
+/// when the topic was paused, 0 means the topic is not paused
+pub fn get_topic_paused_since(hash: &H256) -> u64 {
+    database::get::<_, u64>(get_key(PRE_TOPIC_PAUSED, hash.as_ref())).unwrap_or(0)
+}
+
+/// panics if the contract or the topic is paused
+pub fn check_not_paused(hash: &H256) {
+    assert!(!is_paused());
+    assert_eq!(get_topic_paused_since(hash), 0);
+}
+
+/// halt the voting of a topic before it ends, only the pausers have the right to invoke
+/// not allowed while the contract is paused, so the two pauses never overlap
+pub fn pause_topic(hash: &H256) -> bool {
+    assert!(check_role_witness(ROLE_PAUSER));
+    assert!(!is_paused());
+    let info = get_topic_info(hash).expect("not exist topic info");
+    assert_eq!(info.status, 1);
+    let cur = timestamp();
+    assert!(cur < info.voting_end_time());
+    assert_eq!(get_topic_paused_since(hash), 0);
+    database::put(get_key(PRE_TOPIC_PAUSED, hash.as_ref()), cur);
+    EventBuilder::new()
+        .string("pauseTopic")
+        .h256(hash)
+        .number(cur as U128)
+        .notify();
+    true
+}
+
+/// resume the voting of a topic, only the pausers have the right to invoke
+/// end_time and reveal_end_time are extended by the paused duration after the voting started
+pub fn unpause_topic(hash: &H256) -> bool {
+    assert!(check_role_witness(ROLE_PAUSER));
+    assert!(!is_paused());
+    let paused_since = get_topic_paused_since(hash);
+    assert_ne!(paused_since, 0);
+    let mut info = get_topic_info(hash).expect("not exist topic info");
+    extend_voting(
+        &mut info,
+        timestamp().saturating_sub(paused_since.max(info.start_time)),
+    );
+    let end_time = info.end_time;
+    put_topic_info(hash, info);
+    database::delete(get_key(PRE_TOPIC_PAUSED, hash.as_ref()));
+    EventBuilder::new()
+        .string("unpauseTopic")
+        .h256(hash)
+        .number(end_time as U128)
+        .notify();
+    true
+}
@@ -0,0 +1,78 @@
+use super::*;
+
+/// vote for a ranked topic with the option indexes, most preferred first
+/// only the consensus and candidate nodes have the right to invoke
+/// the ranking may leave out the options the voter does not want to rank
+pub fn vote_topic_ranked(hash: &H256, voter: Address, ranking: Vec<u32>) -> bool {
+    let info = check_vote(hash, &voter);
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    let mut ballot = Ballot::new(VOTED_RANKED);
+    ballot.ranking = ranking;
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicRanked")
+        .h256(hash)
+        .address(&voter)
+        .notify();
+    true
+}
+
+pub fn check_ranking(info: &TopicInfo, ranking: &[u32]) {
+    assert!(!ranking.is_empty());
+    for (i, option) in ranking.iter().enumerate() {
+        assert!((*option as usize) < info.config.options.len());
+        assert!(
+            !ranking[..i].contains(option),
+            "duplicated option in ranking"
+        );
+    }
+}
+
+/// weighted instant runoff, every round counts each ballot for its most preferred option
+/// still in the runoff and eliminates the option with the least weight, until one option has
+/// more than half of the continuing weight or only one option is left
+/// ties for the least weight eliminate the option with the highest index
+pub fn run_instant_runoff(option_num: usize, voted_info: &[VotedInfo]) -> Vec<RunoffRound> {
+    let mut active = vec![true; option_num];
+    let mut rounds = Vec::with_capacity(option_num);
+    loop {
+        let mut round = RunoffRound {
+            tallies: vec![0; option_num],
+            exhausted: 0,
+            eliminated: vec![],
+        };
+        for i in voted_info.iter().filter(|i| !i.abstain) {
+            let weight = i.total_weight();
+            match i.ranking.iter().find(|o| active[**o as usize]) {
+                Some(o) => round.tallies[*o as usize] += weight,
+                None => round.exhausted += weight,
+            }
+        }
+        let continuing: u64 = round.tallies.iter().sum();
+        let mut leader = None;
+        let mut loser = None;
+        for o in (0..option_num).filter(|o| active[*o]) {
+            if leader.map_or(true, |l: usize| round.tallies[o] > round.tallies[l]) {
+                leader = Some(o);
+            }
+            if loser.map_or(true, |l: usize| round.tallies[o] <= round.tallies[l]) {
+                loser = Some(o);
+            }
+        }
+        let active_num = active.iter().filter(|a| **a).count();
+        let decided = match leader {
+            Some(l) => round.tallies[l] * 2 > continuing,
+            None => true,
+        };
+        if decided || continuing == 0 || active_num <= 1 {
+            rounds.push(round);
+            return rounds;
+        }
+        let loser = loser.unwrap();
+        active[loser] = false;
+        round.eliminated.push(loser as u32);
+        rounds.push(round);
+    }
+}
@@ -0,0 +1,133 @@
+use super::*;
+
+pub const QUORUM_NONE: u8 = 0;
+/// the participating weight must reach TopicConfig.quorum
+pub const QUORUM_ABSOLUTE: u8 = 1;
+/// the participating weight must reach TopicConfig.quorum percent of the total eligible weight
+pub const QUORUM_PERCENT: u8 = 2;
+
+/// more than half of the approve and reject weight approves
+pub const THRESHOLD_SIMPLE_MAJORITY: u8 = 0;
+/// at least two thirds of the approve and reject weight approves
+pub const THRESHOLD_TWO_THIRDS: u8 = 1;
+/// more than half of the approve and reject weight and more than half of the approve and
+/// reject nodes approve
+pub const THRESHOLD_DUAL_MAJORITY: u8 = 2;
+
+pub const RESULT_PENDING: u8 = 0;
+pub const RESULT_PASSED: u8 = 1;
+pub const RESULT_REJECTED: u8 = 2;
+pub const RESULT_QUORUM_NOT_MET: u8 = 3;
+pub const RESULT_CANCELED: u8 = 4;
+/// a guardian vetoed the passed topic, see vetoTopic
+pub const RESULT_VETOED: u8 = 5;
+/// the passed topic was taken out of the timelock queue, see cancelQueuedTopic
+pub const RESULT_EXECUTION_CANCELED: u8 = 6;
+
+pub fn check_result_rule(config: &TopicConfig) {
+    match config.quorum_type {
+        QUORUM_NONE | QUORUM_ABSOLUTE => {}
+        QUORUM_PERCENT => assert!(config.quorum <= 100),
+        _ => panic!("unknown quorum type"),
+    }
+    assert!(config.threshold <= THRESHOLD_DUAL_MAJORITY);
+    if config.topic_type == TOPIC_TYPE_RANKED {
+        assert_ne!(config.threshold, THRESHOLD_DUAL_MAJORITY);
+    }
+}
+
+/// 0: pending, 1: passed, 2: rejected, 3: quorum not met, 4: canceled, 5: vetoed,
+/// 6: execution canceled
+/// the sealed result is returned once the topic is finalized
+pub fn get_topic_result(hash: &H256) -> u8 {
+    let info = get_topic_info(hash).expect("not exist topic info");
+    if info.tally.finalized_time != 0 {
+        return info.tally.result;
+    }
+    let total_weight = get_total_eligible_weight(hash, &info);
+    compute_result(&info, total_weight, timestamp())
+}
+
+/// work out the result of the topic from the stored tally
+/// total_weight is the sum of the weight of all the eligible voters
+pub fn compute_result(info: &TopicInfo, total_weight: u64, cur: u64) -> u8 {
+    if info.status == 0 {
+        return RESULT_CANCELED;
+    }
+    if cur < info.voting_end_time() {
+        return RESULT_PENDING;
+    }
+    let tally = &info.tally;
+    if info.config.topic_type == TOPIC_TYPE_RANKED {
+        return compute_ranked_result(info, total_weight);
+    }
+    // the leading option of a multiple-choice or approval topic must meet the threshold of the
+    // weight of all the votes which are not abstain
+    let (yes, total, yes_count, total_count) = if info.config.topic_type != TOPIC_TYPE_BINARY {
+        let mut leading = 0;
+        for (i, weight) in tally.options.iter().enumerate() {
+            if *weight > tally.options[leading] {
+                leading = i;
+            }
+        }
+        (
+            tally.options.get(leading).cloned().unwrap_or(0),
+            tally.voted,
+            tally.option_counts.get(leading).cloned().unwrap_or(0),
+            tally.voted_count,
+        )
+    } else {
+        (
+            info.approve,
+            info.approve + info.reject,
+            tally.approve_count,
+            tally.approve_count + tally.reject_count,
+        )
+    };
+    if !quorum_met(&info.config, tally.turnout, total_weight) {
+        return RESULT_QUORUM_NOT_MET;
+    }
+    if total == 0 || !threshold_met(info.config.threshold, yes, total) {
+        return RESULT_REJECTED;
+    }
+    if info.config.threshold == THRESHOLD_DUAL_MAJORITY && yes_count * 2 <= total_count {
+        return RESULT_REJECTED;
+    }
+    RESULT_PASSED
+}
+
+/// the result of a ranked topic is known once finalizeTopic ran the instant runoff
+/// it passes if the winner of the last round meets the threshold of the continuing ballots
+fn compute_ranked_result(info: &TopicInfo, total_weight: u64) -> u8 {
+    let last = match info.tally.rounds.last() {
+        Some(round) => round,
+        None => return RESULT_PENDING,
+    };
+    if !quorum_met(&info.config, info.tally.turnout, total_weight) {
+        return RESULT_QUORUM_NOT_MET;
+    }
+    let yes = last.tallies.iter().max().cloned().unwrap_or(0);
+    let total = last.tallies.iter().sum::<u64>();
+    if total != 0 && threshold_met(info.config.threshold, yes, total) {
+        RESULT_PASSED
+    } else {
+        RESULT_REJECTED
+    }
+}
+
+fn quorum_met(config: &TopicConfig, turnout: u64, total_weight: u64) -> bool {
+    match config.quorum_type {
+        QUORUM_ABSOLUTE => turnout >= config.quorum,
+        QUORUM_PERCENT => turnout as U128 * 100 >= total_weight as U128 * config.quorum as U128,
+        _ => true,
+    }
+}
+
+fn threshold_met(threshold: u8, yes: u64, total: u64) -> bool {
+    let (yes, total) = (yes as U128, total as U128);
+    match threshold {
+        THRESHOLD_TWO_THIRDS => yes * 3 >= total * 2,
+        // THRESHOLD_DUAL_MAJORITY also checks the node count in compute_result
+        _ => yes * 2 > total,
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+/// the admin set of the contract, managed with setAdmins instead of grantRole
+pub const ROLE_ADMIN: u8 = 0;
+/// can cancel the topics of any creator
+pub const ROLE_MODERATOR: u8 = 1;
+/// can pause the whole contract or single topics
+pub const ROLE_PAUSER: u8 = 2;
+/// migrate needs the confirmations of threshold admins who have this role
+pub const ROLE_UPGRADER: u8 = 3;
+
+pub fn has_role(role: u8, addr: &Address) -> bool {
+    if role == ROLE_ADMIN {
+        return is_admin(addr);
+    }
+    get_role_members(role).contains(addr)
+}
+
+pub fn get_role_members(role: u8) -> Vec<Address> {
+    if role == ROLE_ADMIN {
+        return get_admins().admins;
+    }
+    database::get::<_, Vec<Address>>(get_key(PRE_ROLE_MEMBERS, &[role])).unwrap_or(vec![])
+}
+
+/// true if one of the members of the role signed the transaction
+pub fn check_role_witness(role: u8) -> bool {
+    get_role_members(role).iter().any(|m| check_witness(m))
+}
+
+/// only an admin proposal can invoke
+pub fn grant_role(role: u8, addr: &Address) -> bool {
+    check_role(role);
+    let mut members = get_role_members(role);
+    assert!(!members.contains(addr));
+    members.push(addr.clone());
+    database::put(get_key(PRE_ROLE_MEMBERS, &[role]), members);
+    EventBuilder::new()
+        .string("grantRole")
+        .number(role as U128)
+        .address(addr)
+        .notify();
+    true
+}
+
+/// only an admin proposal can invoke
+pub fn revoke_role(role: u8, addr: &Address) -> bool {
+    check_role(role);
+    let mut members = get_role_members(role);
+    let pos = members
+        .iter()
+        .position(|m| m == addr)
+        .expect("the address does not have the role");
+    members.remove(pos);
+    database::put(get_key(PRE_ROLE_MEMBERS, &[role]), members);
+    EventBuilder::new()
+        .string("revokeRole")
+        .number(role as U128)
+        .address(addr)
+        .notify();
+    true
+}
+
+fn check_role(role: u8) {
+    assert!(role > ROLE_ADMIN && role <= ROLE_UPGRADER);
+}
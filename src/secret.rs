@@ -0,0 +1,102 @@
+use super::*;
+use ostd::runtime::sha256;
+
+/// commit a hash of the ballot to a secret ballot topic before end_time
+/// commitment is sha256(voter || ballot || salt), see ballot_commitment
+/// committing again replaces the previous commitment
+pub fn commit_vote(hash: &H256, voter: Address, commitment: H256) -> bool {
+    let info = check_voter(hash, &voter);
+    assert!(info.config.secret_ballot);
+    let mut commitments = get_commitments(hash);
+    let mut has_committed = false;
+    for c in commitments.iter_mut() {
+        if c.voter == voter {
+            c.commitment = commitment.clone();
+            has_committed = true;
+        }
+    }
+    if !has_committed {
+        commitments.push(Commitment {
+            voter,
+            commitment: commitment.clone(),
+            revealed: false,
+        });
+    }
+    put_commitments(hash, commitments);
+    EventBuilder::new()
+        .string("commitVote")
+        .h256(hash)
+        .address(&voter)
+        .h256(&commitment)
+        .notify();
+    true
+}
+
+/// reveal a committed ballot between end_time and reveal_end_time
+/// the ballot is tallied if it matches the commitment
+pub fn reveal_vote(hash: &H256, voter: Address, ballot: Ballot, salt: &[u8]) -> bool {
+    assert!(check_witness(&voter));
+    check_not_paused(hash);
+    let info = get_topic_info(hash).expect("not exist topic info");
+    assert!(info.config.secret_ballot);
+    assert!(info.status == 1);
+    let cur = timestamp();
+    assert!(info.end_time <= cur, "not in the reveal period");
+    assert!(
+        info.config.reveal_end_time > cur,
+        "not in the reveal period"
+    );
+    let mut commitments = get_commitments(hash);
+    let c = commitments
+        .iter_mut()
+        .find(|c| c.voter == voter)
+        .expect("no commitment of the voter");
+    assert!(!c.revealed);
+    assert_eq!(
+        c.commitment,
+        ballot_commitment(&voter, &ballot, salt),
+        "ballot does not match the commitment"
+    );
+    c.revealed = true;
+    put_commitments(hash, commitments);
+
+    let weight = get_topic_voter_weight(hash, &info, &voter);
+    let mut vi = VotedInfo::new(voter, weight);
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("revealVote")
+        .h256(hash)
+        .address(&voter)
+        .number(ballot.choice as U128)
+        .number(ballot.option as U128)
+        .notify();
+    true
+}
+
+pub fn ballot_commitment(voter: &Address, ballot: &Ballot, salt: &[u8]) -> H256 {
+    let mut sink = Sink::new(64);
+    sink.write(voter);
+    sink.write(ballot);
+    sink.write(salt);
+    sha256(sink.bytes())
+}
+
+pub fn get_commitments(hash: &H256) -> Vec<Commitment> {
+    let key = get_key(PRE_COMMITMENT, hash.as_ref());
+    database::get::<_, Vec<Commitment>>(key).unwrap_or(vec![])
+}
+
+/// the voters who committed a ballot but have not revealed it
+pub fn get_unrevealed_commitments(hash: &H256) -> Vec<Address> {
+    get_commitments(hash)
+        .into_iter()
+        .filter(|c| !c.revealed)
+        .map(|c| c.voter)
+        .collect()
+}
+
+fn put_commitments(hash: &H256, commitments: Vec<Commitment>) {
+    let key = get_key(PRE_COMMITMENT, hash.as_ref());
+    database::put(key, commitments);
+}
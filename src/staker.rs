@@ -0,0 +1,64 @@
+use super::*;
+
+/// vote with the ONT the staker authorized to the node
+/// the weight is taken out of the node's vote on the topic
+/// the authorized ONT is read live, so topics with a weight snapshot do not accept staker votes
+pub fn vote_topic_as_staker(hash: &H256, staker: Address, node: Address, ballot: Ballot) -> bool {
+    assert!(check_witness(&staker));
+    assert_ne!(staker, node);
+    let info = check_topic_active(hash);
+    assert!(!info.config.secret_ballot);
+    assert!(!info.config.custom_voters);
+    assert_eq!(info.config.weight_strategy, WEIGHT_STAKE);
+    assert_eq!(info.config.snapshot_mode, SNAPSHOT_NONE);
+    let weight = get_authorized_weight(&node, &staker);
+    assert!(weight > 0, "no authorized ONT");
+    let mut vi = VotedInfo::new(staker, weight);
+    vi.authorized_to = node;
+    apply_ballot(&info, &mut vi, &ballot);
+    update_voted_info(hash, vi, info);
+    EventBuilder::new()
+        .string("voteTopicAsStaker")
+        .h256(hash)
+        .address(&staker)
+        .address(&node)
+        .number(ballot.choice as U128)
+        .number(ballot.option as U128)
+        .notify();
+    true
+}
+
+/// take back the vote the staker cast through the node before the topic ends
+pub fn revoke_staker_vote(hash: &H256, staker: Address, node: Address) -> bool {
+    assert!(check_witness(&staker));
+    remove_vote(hash, &staker, &node);
+    EventBuilder::new()
+        .string("revokeStakerVote")
+        .h256(hash)
+        .address(&staker)
+        .address(&node)
+        .notify();
+    true
+}
+
+/// the ONT the staker authorized to the node, 0 if the node is not a consensus or candidate node
+pub fn get_authorized_weight(node: &Address, staker: &Address) -> u64 {
+    let peer_info = get_peer_info(node);
+    if peer_info.status != 1 && peer_info.status != 2 {
+        return 0;
+    }
+    if &peer_info.peer_pubkey_addr != node {
+        return 0;
+    }
+    let authorize_info = governance::get_authorize_info(&peer_info.peer_pubkey, staker);
+    authorize_info.consensus_pos + authorize_info.candidate_pos
+}
+
+/// the weight of the stakers of the node who voted themselves
+pub fn get_staker_voted_weight(voted_info: &[VotedInfo], node: &Address) -> u64 {
+    voted_info
+        .iter()
+        .filter(|i| &i.authorized_to == node)
+        .map(|i| i.weight)
+        .sum()
+}
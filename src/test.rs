@@ -46,9 +46,15 @@ fn test_topic_info() {
     let mut source = Source::new(data.as_slice());
     let boo: bool = source.read().unwrap();
     assert!(boo);
-    let topic_info: TopicInfo = source.read().unwrap();
+    let legacy: LegacyTopicInfo = source.read().unwrap();
+    let topic_info = TopicInfo::from(legacy);
+    assert_eq!(topic_info.config.topic_type, TOPIC_TYPE_BINARY);
     println!("{}", topic_info.approve);
     println!("{}", topic_info.reject);
+    // getTopicInfo still returns the layout it returned before topic config existed
+    let mut sink = Sink::new(64);
+    sink.write(LegacyTopicInfo::from(topic_info));
+    assert_eq!(sink.bytes(), &data[1..]);
 }
 
 #[test]
@@ -60,6 +66,26 @@ fn test_voter_info() {
     let info: Vec<VotedInfo> = parser.read().unwrap();
 }
 
+#[test]
+fn test_voted_info_legacy() {
+    let plain = new_voted_info(2, 100, true, 0, false);
+    assert!(plain.is_legacy());
+    // abstain, split, delegated and reasoned votes need the layout of getVotedAddressV2
+    assert!(!new_voted_info(2, 100, false, 0, true).is_legacy());
+    let mut split = new_voted_info(2, 100, false, 0, false);
+    split.shares = vec![60, 40];
+    assert!(!split.is_legacy());
+    let mut delegated = new_voted_info(2, 100, true, 0, false);
+    delegated.delegated = vec![VoterWeight {
+        voter: Address::repeat_byte(3),
+        weight: 10,
+    }];
+    assert!(!delegated.is_legacy());
+    let mut reasoned = new_voted_info(2, 100, true, 0, false);
+    reasoned.rationale.text = b"too expensive".to_vec();
+    assert!(!reasoned.is_legacy());
+}
+
 #[test]
 fn test_topic() {
     let data =
@@ -93,3 +119,1328 @@ fn test_create_topic() {
     handle.witness(&[voter]);
     assert!(vote_topic(&hash, voter, true));
 }
+
+fn new_topic_info(config: TopicConfig) -> TopicInfo {
+    TopicInfo {
+        gov_node_addr: Address::repeat_byte(1),
+        topic_title: b"title".to_vec(),
+        topic_detail: b"detail".to_vec(),
+        voters: vec![],
+        start_time: 1,
+        end_time: 4,
+        approve: 0,
+        reject: 0,
+        status: 1,
+        hash: H256::repeat_byte(1),
+        tally: TopicTally {
+            options: vec![0; config.options.len()],
+            option_counts: vec![0; config.options.len()],
+            ..TopicTally::default()
+        },
+        execution: TopicExecution::default(),
+        pause_periods: 0,
+        config,
+    }
+}
+
+fn new_voted_info(
+    voter: u8,
+    weight: u64,
+    approve_or_reject: bool,
+    option: u32,
+    abstain: bool,
+) -> VotedInfo {
+    let mut voted_info = VotedInfo::new(Address::repeat_byte(voter), weight);
+    voted_info.approve_or_reject = approve_or_reject;
+    voted_info.option = option;
+    voted_info.abstain = abstain;
+    voted_info
+}
+
+#[test]
+fn test_tally_multi_choice() {
+    let config = TopicConfig {
+        topic_type: TOPIC_TYPE_MULTI_CHOICE,
+        options: vec![b"plan a".to_vec(), b"plan b".to_vec(), b"plan c".to_vec()],
+        ..TopicConfig::default()
+    };
+    let mut topic_info = new_topic_info(config);
+    let voted_info = vec![
+        new_voted_info(2, 100, false, 2, false),
+        new_voted_info(3, 50, false, 0, false),
+        new_voted_info(4, 30, false, 2, false),
+    ];
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.tally.options, vec![50, 0, 130]);
+    assert_eq!(topic_info.approve, 0);
+    assert_eq!(topic_info.reject, 0);
+    assert_eq!(topic_info.tally.abstain, 0);
+}
+
+#[test]
+fn test_tally_abstain() {
+    let mut topic_info = new_topic_info(TopicConfig::default());
+    let voted_info = vec![
+        new_voted_info(2, 100, true, 0, false),
+        new_voted_info(3, 40, true, 0, true),
+    ];
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.approve, 100);
+    assert_eq!(topic_info.reject, 0);
+    assert_eq!(topic_info.tally.abstain, 40);
+}
+
+#[test]
+fn test_compute_result() {
+    let config = TopicConfig {
+        quorum_type: QUORUM_PERCENT,
+        quorum: 50,
+        threshold: THRESHOLD_TWO_THIRDS,
+        ..TopicConfig::default()
+    };
+    let mut topic_info = new_topic_info(config);
+    topic_info.approve = 300;
+    topic_info.reject = 200;
+    topic_info.tally.turnout = 500;
+    assert_eq!(compute_result(&topic_info, 1000, 3), RESULT_PENDING);
+    assert_eq!(compute_result(&topic_info, 1000, 4), RESULT_REJECTED);
+    assert_eq!(compute_result(&topic_info, 1001, 4), RESULT_QUORUM_NOT_MET);
+    topic_info.approve = 400;
+    topic_info.tally.turnout = 600;
+    assert_eq!(compute_result(&topic_info, 1000, 4), RESULT_PASSED);
+    topic_info.status = 0;
+    assert_eq!(compute_result(&topic_info, 1000, 4), RESULT_CANCELED);
+}
+
+#[test]
+fn test_resolve_delegate() {
+    let delegation = |delegator: u8, delegate: u8| Delegation {
+        delegator: Address::repeat_byte(delegator),
+        delegate: Address::repeat_byte(delegate),
+    };
+    let delegations = vec![
+        delegation(2, 3),
+        delegation(3, 4),
+        delegation(5, 6),
+        delegation(6, 5),
+    ];
+    let voted_info = vec![new_voted_info(4, 100, true, 0, false)];
+    let resolve = |delegator: u8| {
+        resolve_delegate(&delegations, &Address::repeat_byte(delegator), &voted_info)
+    };
+    assert_eq!(resolve(2), Some(0));
+    assert_eq!(resolve(3), Some(0));
+    assert_eq!(resolve(5), None);
+    assert_eq!(resolve(7), None);
+    assert!(!has_cycle(&delegations, &Address::repeat_byte(2)));
+    assert!(has_cycle(&delegations, &Address::repeat_byte(5)));
+}
+
+#[test]
+fn test_delegations_after_end() {
+    let handle = build_runtime();
+    let hash = put_test_topic(0, 10);
+    let later = put_test_topic(1, 30);
+    let last = put_test_topic(2, 40);
+    let (node, first, second) = (
+        Address::repeat_byte(2),
+        Address::repeat_byte(3),
+        Address::repeat_byte(4),
+    );
+    handle.timestamp(5);
+    record_delegation_change(node, first);
+    // the change after the end of the topic does not count for it
+    handle.timestamp(12);
+    record_delegation_change(node, second);
+    handle.timestamp(20);
+    let delegations = get_effective_delegations(&hash);
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations[0].delegate, first);
+    handle.timestamp(35);
+    record_delegation_change(node, Address::new([0u8; 20]));
+    handle.timestamp(41);
+    assert_eq!(get_effective_delegations(&hash)[0].delegate, first);
+    assert_eq!(get_effective_delegations(&later)[0].delegate, second);
+    assert!(get_effective_delegations(&last).is_empty());
+}
+
+#[test]
+fn test_strategy_weight() {
+    assert_eq!(strategy_weight(WEIGHT_STAKE, 100, 300), 400);
+    assert_eq!(strategy_weight(WEIGHT_ONE_NODE_ONE_VOTE, 100, 300), 1);
+    assert_eq!(strategy_weight(WEIGHT_INIT_POS, 100, 300), 100);
+    assert_eq!(strategy_weight(WEIGHT_SQRT_STAKE, 100, 300), 20);
+    assert_eq!(isqrt(0), 0);
+    assert_eq!(isqrt(15), 3);
+    assert_eq!(isqrt(16), 4);
+    assert_eq!(isqrt(u64::max_value()), 4294967295);
+}
+
+#[test]
+fn test_dual_majority() {
+    let config = TopicConfig {
+        threshold: THRESHOLD_DUAL_MAJORITY,
+        ..TopicConfig::default()
+    };
+    let mut topic_info = new_topic_info(config);
+    let mut voted_info = vec![
+        new_voted_info(2, 1000, true, 0, false),
+        new_voted_info(3, 100, false, 0, false),
+        new_voted_info(4, 100, false, 0, false),
+    ];
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.tally.approve_count, 1);
+    assert_eq!(topic_info.tally.reject_count, 2);
+    assert_eq!(compute_result(&topic_info, 1200, 4), RESULT_REJECTED);
+
+    voted_info[0].delegated.push(VoterWeight {
+        voter: Address::repeat_byte(5),
+        weight: 10,
+    });
+    voted_info[0].delegated.push(VoterWeight {
+        voter: Address::repeat_byte(6),
+        weight: 10,
+    });
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.approve, 1020);
+    assert_eq!(topic_info.tally.approve_count, 3);
+    assert_eq!(compute_result(&topic_info, 1220, 4), RESULT_PASSED);
+}
+
+#[test]
+fn test_instant_runoff() {
+    let ranked = |voter: u8, weight: u64, ranking: Vec<u32>| {
+        let mut voted_info = VotedInfo::new(Address::repeat_byte(voter), weight);
+        voted_info.option = ranking[0];
+        voted_info.ranking = ranking;
+        voted_info
+    };
+    let voted_info = vec![
+        ranked(2, 40, vec![0, 1]),
+        ranked(3, 35, vec![1, 2]),
+        ranked(4, 25, vec![2, 1]),
+        ranked(5, 10, vec![2]),
+    ];
+    let rounds = run_instant_runoff(3, &voted_info);
+    assert_eq!(rounds.len(), 2);
+    assert_eq!(rounds[0].tallies, vec![40, 35, 35]);
+    assert_eq!(rounds[0].eliminated, vec![2]);
+    assert_eq!(rounds[1].tallies, vec![40, 60, 0]);
+    assert_eq!(rounds[1].exhausted, 10);
+    assert!(rounds[1].eliminated.is_empty());
+}
+
+#[test]
+fn test_tally_approval() {
+    let config = TopicConfig {
+        topic_type: TOPIC_TYPE_APPROVAL,
+        options: vec![
+            b"feature a".to_vec(),
+            b"feature b".to_vec(),
+            b"feature c".to_vec(),
+        ],
+        ..TopicConfig::default()
+    };
+    let mut topic_info = new_topic_info(config);
+    let approval = |voter: u8, weight: u64, approvals: Vec<u32>| {
+        let mut voted_info = VotedInfo::new(Address::repeat_byte(voter), weight);
+        voted_info.approvals = approvals;
+        voted_info
+    };
+    let voted_info = vec![
+        approval(2, 100, vec![0, 2]),
+        approval(3, 50, vec![2]),
+        approval(4, 30, vec![1]),
+    ];
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.tally.options, vec![100, 30, 150]);
+    assert_eq!(topic_info.tally.option_counts, vec![1, 1, 2]);
+    assert_eq!(topic_info.tally.voted, 180);
+    assert_eq!(compute_result(&topic_info, 180, 4), RESULT_PASSED);
+}
+
+#[test]
+fn test_tally_split() {
+    let mut topic_info = new_topic_info(TopicConfig::default());
+    let mut split = VotedInfo::new(Address::repeat_byte(2), 1001);
+    split.shares = vec![70, 30];
+    let voted_info = vec![split, new_voted_info(3, 100, false, 0, false)];
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.approve, 701);
+    assert_eq!(topic_info.reject, 400);
+    assert_eq!(topic_info.tally.approve_count, 1);
+    assert_eq!(topic_info.tally.reject_count, 1);
+    assert_eq!(topic_info.tally.voted, 1101);
+    assert_eq!(split_weight(10, &[33, 33, 34]), vec![3, 3, 4]);
+}
+
+#[test]
+fn test_conviction() {
+    assert_eq!(conviction_multiplier(0), 1);
+    assert_eq!(conviction_lock_periods(0), 0);
+    assert_eq!(conviction_multiplier(3), 4);
+    assert_eq!(conviction_lock_periods(3), 4);
+    let mut topic_info = new_topic_info(TopicConfig::default());
+    let mut voted_info = new_voted_info(2, 100, true, 0, false);
+    voted_info.conviction = 2;
+    tally_votes(&mut topic_info, &[voted_info]);
+    assert_eq!(topic_info.approve, 300);
+}
+
+#[test]
+fn test_conviction_quorum() {
+    let config = TopicConfig {
+        quorum_type: QUORUM_PERCENT,
+        quorum: 50,
+        ..TopicConfig::default()
+    };
+    let mut topic_info = new_topic_info(config);
+    let mut voted_info = new_voted_info(2, 100, true, 0, false);
+    voted_info.conviction = 5;
+    tally_votes(&mut topic_info, &[voted_info]);
+    assert_eq!(topic_info.approve, 600);
+    // the locked 100 of 1000 is still only a turnout of 10 percent
+    assert_eq!(topic_info.tally.turnout, 100);
+    assert_eq!(compute_result(&topic_info, 1000, 4), RESULT_QUORUM_NOT_MET);
+}
+
+#[test]
+#[should_panic(expected = "conviction needs the stake weight strategy")]
+fn test_conviction_one_node_one_vote() {
+    let info = new_topic_info(TopicConfig {
+        weight_strategy: WEIGHT_ONE_NODE_ONE_VOTE,
+        ..TopicConfig::default()
+    });
+    let mut vi = new_voted_info(2, 1, false, 0, false);
+    let mut ballot = Ballot::new(VOTED_APPROVE);
+    ballot.conviction = 1;
+    apply_ballot(&info, &mut vi, &ballot);
+}
+
+#[test]
+fn test_rationale() {
+    let info = new_topic_info(TopicConfig::default());
+    let mut vi = new_voted_info(2, 100, false, 0, false);
+    let mut ballot = Ballot::new(VOTED_APPROVE);
+    ballot.rationale.uri = b"ipfs://rationale".to_vec();
+    ballot.rationale.content_hash = H256::repeat_byte(3);
+    apply_ballot(&info, &mut vi, &ballot);
+    assert!(vi.approve_or_reject);
+    assert_eq!(vi.rationale.uri, b"ipfs://rationale".to_vec());
+}
+
+#[test]
+#[should_panic]
+fn test_rationale_without_hash() {
+    let info = new_topic_info(TopicConfig::default());
+    let mut vi = new_voted_info(2, 100, false, 0, false);
+    let mut ballot = Ballot::new(VOTED_APPROVE);
+    ballot.rationale.uri = b"ipfs://rationale".to_vec();
+    apply_ballot(&info, &mut vi, &ballot);
+}
+
+#[test]
+#[should_panic]
+fn test_check_actions() {
+    let action = ProposalAction {
+        vm_type: VM_TYPE_NEO + 1,
+        target: Address::repeat_byte(2),
+        method: b"upgrade".to_vec(),
+        args: vec![],
+    };
+    check_actions(&[action]);
+}
+
+#[test]
+fn test_admin_set_threshold() {
+    let admins = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+    check_admin_set(&admins, 1);
+    check_admin_set(&admins, 2);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_set_threshold_too_high() {
+    let admins = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+    check_admin_set(&admins, 3);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_set_duplicate() {
+    let admins = vec![Address::repeat_byte(1), Address::repeat_byte(1)];
+    check_admin_set(&admins, 1);
+}
+
+fn put_test_admins(threshold: u32) -> Vec<Address> {
+    let admins = vec![
+        Address::repeat_byte(11),
+        Address::repeat_byte(12),
+        Address::repeat_byte(13),
+    ];
+    database::put(
+        KEY_ADMINS,
+        AdminSet {
+            admins: admins.clone(),
+            threshold,
+        },
+    );
+    admins
+}
+
+#[test]
+fn test_get_admin() {
+    let _handle = build_runtime();
+    assert_eq!(get_admin(), ADMIN);
+    let admins = put_test_admins(2);
+    assert_eq!(get_admin(), admins[0]);
+}
+
+fn delay_args(delay: u64) -> Vec<u8> {
+    let mut sink = Sink::new(16);
+    sink.write(delay);
+    sink.bytes().to_vec()
+}
+
+#[test]
+fn test_admin_proposal_threshold() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(2);
+    let delay = 3 * 24 * 3600;
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"setTimelockDelay",
+        &delay_args(delay),
+        200
+    ));
+    let proposal = get_admin_proposal(0).unwrap();
+    assert!(!proposal.executed);
+    assert_eq!(get_timelock_delay(), DEFAULT_TIMELOCK_DELAY);
+    handle.witness(&[admins[1]]);
+    assert!(confirm_admin_action(0, admins[1]));
+    let proposal = get_admin_proposal(0).unwrap();
+    assert!(proposal.executed);
+    assert_eq!(proposal.confirmations, vec![admins[0], admins[1]]);
+    assert_eq!(get_timelock_delay(), delay);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_proposal_executed_once() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(1);
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"setTimelockDelay",
+        &delay_args(MIN_TIMELOCK_DELAY),
+        200
+    ));
+    assert!(get_admin_proposal(0).unwrap().executed);
+    handle.witness(&[admins[1]]);
+    confirm_admin_action(0, admins[1]);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_proposal_expired() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(2);
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"setTimelockDelay",
+        &delay_args(MIN_TIMELOCK_DELAY),
+        200
+    ));
+    handle.timestamp(200);
+    handle.witness(&[admins[1]]);
+    confirm_admin_action(0, admins[1]);
+}
+
+#[test]
+#[should_panic]
+fn test_admin_proposal_not_admin() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    put_test_admins(2);
+    let outsider = Address::repeat_byte(14);
+    handle.witness(&[outsider]);
+    propose_admin_action(
+        outsider,
+        b"setTimelockDelay",
+        &delay_args(MIN_TIMELOCK_DELAY),
+        200,
+    );
+}
+
+#[test]
+fn test_admin_proposal_removed_admin() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(2);
+    let delay = 4 * 24 * 3600;
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"setTimelockDelay",
+        &delay_args(delay),
+        200
+    ));
+    // the proposer leaves the set, its confirmation no longer counts
+    set_admins(vec![admins[1], admins[2]], 2);
+    handle.witness(&[admins[1]]);
+    assert!(confirm_admin_action(0, admins[1]));
+    assert!(!get_admin_proposal(0).unwrap().executed);
+    assert_eq!(get_timelock_delay(), DEFAULT_TIMELOCK_DELAY);
+    handle.witness(&[admins[2]]);
+    assert!(confirm_admin_action(0, admins[2]));
+    assert!(get_admin_proposal(0).unwrap().executed);
+    assert_eq!(get_timelock_delay(), delay);
+}
+
+#[test]
+fn test_staker_weight_split() {
+    let mut topic_info = new_topic_info(TopicConfig::default());
+    // the node has 100, 30 of it authorized by a staker who voted themselves
+    let node = new_voted_info(2, 100, true, 0, false);
+    let mut staker = new_voted_info(3, 30, false, 0, false);
+    staker.authorized_to = Address::repeat_byte(2);
+    let other = new_voted_info(4, 50, false, 0, false);
+    let mut voted_info = vec![node, staker, other];
+    assert_eq!(
+        get_staker_voted_weight(&voted_info, &Address::repeat_byte(2)),
+        30
+    );
+    assert_eq!(
+        get_staker_voted_weight(&voted_info, &Address::repeat_byte(4)),
+        0
+    );
+    subtract_staker_weights(&mut voted_info);
+    assert_eq!(voted_info[0].weight, 70);
+    assert_eq!(voted_info[1].weight, 30);
+    assert_eq!(voted_info[2].weight, 50);
+    tally_votes(&mut topic_info, &voted_info);
+    assert_eq!(topic_info.approve, 70);
+    assert_eq!(topic_info.reject, 80);
+    // the staker is not a node
+    assert_eq!(topic_info.tally.approve_count, 1);
+    assert_eq!(topic_info.tally.reject_count, 1);
+}
+
+#[test]
+#[should_panic]
+fn test_split_shares_overflow() {
+    let info = new_topic_info(TopicConfig::default());
+    let mut vi = new_voted_info(2, 100, false, 0, false);
+    let mut ballot = Ballot::new(VOTED_SPLIT);
+    // wraps to 100 if summed as u32
+    ballot.shares = vec![u32::MAX, 101];
+    apply_ballot(&info, &mut vi, &ballot);
+}
+
+#[test]
+fn test_vote_lock_per_node() {
+    let handle = build_runtime();
+    handle.timestamp(2);
+    let hash = H256::repeat_byte(7);
+    let info = new_topic_info(TopicConfig::default());
+    let staker = Address::repeat_byte(3);
+    let mut first = new_voted_info(3, 30, true, 0, false);
+    first.authorized_to = Address::repeat_byte(4);
+    first.conviction = 2;
+    let mut second = new_voted_info(3, 20, true, 0, false);
+    second.authorized_to = Address::repeat_byte(5);
+    second.conviction = 1;
+    update_vote_lock(&hash, &info, &first);
+    update_vote_lock(&hash, &info, &second);
+    assert_eq!(get_vote_locks(&staker).len(), 2);
+    let lock = get_vote_lock(&hash, &staker, &Address::repeat_byte(4)).unwrap();
+    assert_eq!(lock.amount, 30);
+    assert_eq!(lock.unlock_time, 4 + 2 * LOCK_PERIOD);
+    assert!(!lock.forfeited);
+    // a vote without its lock does not get the multiplier
+    let mut other = new_voted_info(6, 10, true, 0, false);
+    other.conviction = 3;
+    enforce_vote_lock(&hash, &info, &mut other);
+    assert_eq!(other.conviction, 0);
+}
+
+#[test]
+#[should_panic]
+fn test_timelock_delay_minimum() {
+    let _handle = build_runtime();
+    set_timelock_delay(0);
+}
+
+#[test]
+fn test_veto_deadline() {
+    let _handle = build_runtime();
+    let info = new_topic_info(TopicConfig::default());
+    assert_eq!(veto_deadline(&info), 4 + get_veto_period());
+    set_veto_period(3 * 24 * 3600);
+    assert_eq!(veto_deadline(&info), 4 + 3 * 24 * 3600);
+}
+
+#[test]
+fn test_grant_revoke_role() {
+    let handle = build_runtime();
+    let admins = put_test_admins(1);
+    let moderator = Address::repeat_byte(21);
+    assert!(!has_role(ROLE_MODERATOR, &moderator));
+    assert!(grant_role(ROLE_MODERATOR, &moderator));
+    assert!(has_role(ROLE_MODERATOR, &moderator));
+    assert!(!has_role(ROLE_PAUSER, &moderator));
+    assert!(has_role(ROLE_ADMIN, &admins[0]));
+    handle.witness(&[moderator]);
+    assert!(check_role_witness(ROLE_MODERATOR));
+    assert!(!check_role_witness(ROLE_PAUSER));
+    assert!(revoke_role(ROLE_MODERATOR, &moderator));
+    assert!(!has_role(ROLE_MODERATOR, &moderator));
+    assert!(!check_role_witness(ROLE_MODERATOR));
+}
+
+#[test]
+#[should_panic]
+fn test_grant_admin_role() {
+    let _handle = build_runtime();
+    grant_role(ROLE_ADMIN, &Address::repeat_byte(21));
+}
+
+fn migrate_args() -> Vec<u8> {
+    let mut sink = Sink::new(64);
+    sink.write(b"new code" as &[u8]);
+    sink.write(3 as U128);
+    sink.write("vote");
+    sink.write("2.0");
+    sink.write("author");
+    sink.write("email");
+    sink.write("desc");
+    sink.bytes().to_vec()
+}
+
+#[test]
+#[should_panic(expected = "not an admin who can confirm the method")]
+fn test_migrate_needs_upgrader() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(1);
+    handle.witness(&[admins[0]]);
+    propose_admin_action(admins[0], b"migrate", &migrate_args(), 200);
+}
+
+#[test]
+fn test_migrate_confirmations_need_upgrader() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(2);
+    grant_role(ROLE_UPGRADER, &admins[0]);
+    grant_role(ROLE_UPGRADER, &admins[1]);
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"migrate",
+        &migrate_args(),
+        200
+    ));
+    // the proposer is still an admin but no longer an upgrader, its confirmation is dropped
+    revoke_role(ROLE_UPGRADER, &admins[0]);
+    handle.witness(&[admins[1]]);
+    assert!(confirm_admin_action(0, admins[1]));
+    let proposal = get_admin_proposal(0).unwrap();
+    assert_eq!(proposal.confirmations.len(), 2);
+    assert!(!proposal.executed);
+}
+
+#[test]
+#[should_panic(expected = "not an admin who can confirm the method")]
+fn test_migrate_confirm_by_non_upgrader() {
+    let handle = build_runtime();
+    handle.timestamp(100);
+    let admins = put_test_admins(2);
+    grant_role(ROLE_UPGRADER, &admins[0]);
+    handle.witness(&[admins[0]]);
+    assert!(propose_admin_action(
+        admins[0],
+        b"migrate",
+        &migrate_args(),
+        200
+    ));
+    handle.witness(&[admins[1]]);
+    confirm_admin_action(0, admins[1]);
+}
+
+#[test]
+fn test_admin_transfer() {
+    let handle = build_runtime();
+    let admins = put_test_admins(2);
+    let new_admin = Address::repeat_byte(31);
+    assert!(propose_admin(new_admin, admins[0]));
+    assert_eq!(get_pending_admin().unwrap().new_admin, new_admin);
+    handle.witness(&[new_admin]);
+    assert!(accept_admin(new_admin));
+    assert!(get_pending_admin().is_none());
+    let set = get_admins();
+    assert_eq!(set.admins, vec![admins[1], admins[2], new_admin]);
+    assert_eq!(set.threshold, 2);
+}
+
+#[test]
+fn test_cancel_admin_transfer() {
+    let _handle = build_runtime();
+    let admins = put_test_admins(2);
+    assert!(propose_admin(
+        Address::repeat_byte(31),
+        Address::new([0u8; 20])
+    ));
+    assert!(cancel_admin_transfer());
+    assert!(get_pending_admin().is_none());
+    assert!(propose_admin(Address::repeat_byte(32), admins[1]));
+}
+
+#[test]
+#[should_panic]
+fn test_admin_transfer_pending() {
+    let _handle = build_runtime();
+    put_test_admins(2);
+    assert!(propose_admin(
+        Address::repeat_byte(31),
+        Address::new([0u8; 20])
+    ));
+    propose_admin(Address::repeat_byte(32), Address::new([0u8; 20]));
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_by_other() {
+    let handle = build_runtime();
+    put_test_admins(2);
+    let new_admin = Address::repeat_byte(31);
+    assert!(propose_admin(new_admin, Address::new([0u8; 20])));
+    handle.witness(&[Address::repeat_byte(32)]);
+    accept_admin(new_admin);
+}
+
+#[test]
+#[should_panic]
+fn test_accept_admin_replaced_removed() {
+    let handle = build_runtime();
+    let admins = put_test_admins(2);
+    let new_admin = Address::repeat_byte(31);
+    assert!(propose_admin(new_admin, admins[0]));
+    set_admins(vec![admins[1], admins[2]], 2);
+    handle.witness(&[new_admin]);
+    accept_admin(new_admin);
+}
+
+#[test]
+fn test_extend_voting() {
+    let mut info = new_topic_info(TopicConfig::default());
+    extend_voting(&mut info, 10);
+    assert_eq!(info.end_time, 14);
+    assert_eq!(info.voting_end_time(), 14);
+    let mut secret = new_topic_info(TopicConfig {
+        secret_ballot: true,
+        reveal_end_time: 6,
+        ..TopicConfig::default()
+    });
+    extend_voting(&mut secret, 10);
+    assert_eq!(secret.end_time, 14);
+    assert_eq!(secret.voting_end_time(), 16);
+}
+
+fn put_test_topic(num: u32, end_time: u64) -> H256 {
+    let hash = H256::repeat_byte(40 + num as u8);
+    let mut info = new_topic_info(TopicConfig::default());
+    info.end_time = end_time;
+    put_topic_info(&hash, info);
+    database::put(get_key(PRE_TOPIC_HASH, num.to_string().as_bytes()), &hash);
+    database::put(KEY_CUR_HASH_NUM, num + 1);
+    hash
+}
+
+/// a custom voters topic where voter 2 has weight 60 and voter 3 has weight 40
+fn put_custom_topic(num: u32, end_time: u64) -> H256 {
+    let hash = put_test_topic(num, end_time);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.config.custom_voters = true;
+    info.voters = vec![
+        VoterWeight {
+            voter: Address::repeat_byte(2),
+            weight: 60,
+        },
+        VoterWeight {
+            voter: Address::repeat_byte(3),
+            weight: 40,
+        },
+    ];
+    put_topic_info(&hash, info);
+    put_voted_info(&hash, vec![]);
+    hash
+}
+
+fn grant_test_pauser() -> Address {
+    let pauser = Address::repeat_byte(51);
+    grant_role(ROLE_PAUSER, &pauser);
+    pauser
+}
+
+#[test]
+fn test_pause_topic() {
+    let handle = build_runtime();
+    handle.witness(&[grant_test_pauser()]);
+    let hash = put_test_topic(0, 10);
+    handle.timestamp(3);
+    assert!(pause_topic(&hash));
+    assert_eq!(get_topic_paused_since(&hash), 3);
+    handle.timestamp(8);
+    assert!(unpause_topic(&hash));
+    assert_eq!(get_topic_paused_since(&hash), 0);
+    assert_eq!(get_topic_info(&hash).unwrap().end_time, 15);
+}
+
+#[test]
+fn test_pause_contract() {
+    let handle = build_runtime();
+    handle.witness(&[grant_test_pauser()]);
+    let active = put_test_topic(0, 10);
+    let ended = put_test_topic(1, 3);
+    handle.timestamp(5);
+    assert!(set_paused(true));
+    assert!(is_paused());
+    assert_eq!(get_paused_since(), 5);
+    handle.timestamp(12);
+    assert!(set_paused(false));
+    assert!(!is_paused());
+    // only the topic still voting when the pause started gets the 7 seconds back
+    assert_eq!(get_topic_info(&active).unwrap().end_time, 17);
+    assert_eq!(get_topic_info(&ended).unwrap().end_time, 3);
+}
+
+#[test]
+fn test_pause_before_start() {
+    let handle = build_runtime();
+    handle.witness(&[grant_test_pauser()]);
+    let hash = put_test_topic(0, 20);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.start_time = 8;
+    put_topic_info(&hash, info);
+    handle.timestamp(5);
+    assert!(set_paused(true));
+    handle.timestamp(12);
+    assert!(set_paused(false));
+    // only the 4 seconds after the voting started are given back
+    assert_eq!(get_pause_periods().len(), 1);
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(info.end_time, 24);
+    // storing the topic keeps the pause from being given back twice
+    put_topic_info(&hash, info);
+    assert_eq!(get_topic_info(&hash).unwrap().end_time, 24);
+}
+
+#[test]
+fn test_pause_contract_while_topic_paused() {
+    let handle = build_runtime();
+    handle.witness(&[grant_test_pauser()]);
+    let hash = put_test_topic(0, 10);
+    handle.timestamp(3);
+    assert!(pause_topic(&hash));
+    handle.timestamp(5);
+    assert!(set_paused(true));
+    handle.timestamp(7);
+    assert!(set_paused(false));
+    handle.timestamp(8);
+    assert!(unpause_topic(&hash));
+    // the contract pause is inside the pause of the topic, it is not given back twice
+    assert_eq!(get_topic_info(&hash).unwrap().end_time, 15);
+}
+
+#[test]
+#[should_panic]
+fn test_finalize_while_paused() {
+    let handle = build_runtime();
+    handle.witness(&[grant_test_pauser()]);
+    let hash = put_test_topic(0, 3);
+    handle.timestamp(5);
+    assert!(set_paused(true));
+    finalize_topic(&hash);
+}
+
+#[test]
+#[should_panic]
+fn test_pause_without_role() {
+    let handle = build_runtime();
+    handle.witness(&[Address::repeat_byte(52)]);
+    handle.timestamp(5);
+    set_paused(true);
+}
+
+#[test]
+#[should_panic(expected = "voting not ended")]
+fn test_finalize_before_end() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    handle.timestamp(9);
+    finalize_topic(&hash);
+}
+
+#[test]
+fn test_finalize_topic() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    put_voted_info(
+        &hash,
+        vec![
+            new_voted_info(2, 0, true, 0, false),
+            new_voted_info(3, 0, false, 0, false),
+        ],
+    );
+    handle.timestamp(12);
+    assert!(finalize_topic(&hash));
+    let info = get_topic_info(&hash).unwrap();
+    // the weights are refreshed from the voters of the topic
+    assert_eq!(info.approve, 60);
+    assert_eq!(info.reject, 40);
+    assert_eq!(info.tally.result, RESULT_PASSED);
+    assert_eq!(info.tally.finalized_time, 12);
+    assert_eq!(get_topic_result(&hash), RESULT_PASSED);
+    // the sealed result is returned even if the stored tally says otherwise
+    let mut info = get_topic_info(&hash).unwrap();
+    info.approve = 0;
+    put_topic_info(&hash, info);
+    handle.timestamp(20);
+    assert_eq!(get_topic_result(&hash), RESULT_PASSED);
+}
+
+#[test]
+#[should_panic(expected = "already finalized")]
+fn test_finalize_twice() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    put_voted_info(&hash, vec![new_voted_info(2, 60, true, 0, false)]);
+    handle.timestamp(12);
+    assert!(finalize_topic(&hash));
+    finalize_topic(&hash);
+}
+
+/// a secret ballot custom voters topic, committing until 10 and revealing until 15
+fn put_secret_topic(num: u32) -> H256 {
+    let hash = put_custom_topic(num, 10);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.config.secret_ballot = true;
+    info.config.reveal_end_time = 15;
+    put_topic_info(&hash, info);
+    hash
+}
+
+fn commit_test_vote(hash: &H256, voter: u8, ballot: &Ballot) {
+    let voter = Address::repeat_byte(voter);
+    let commitment = ballot_commitment(&voter, ballot, b"salt");
+    assert!(commit_vote(hash, voter, commitment));
+}
+
+#[test]
+fn test_reveal_vote() {
+    let handle = build_runtime();
+    let hash = put_secret_topic(0);
+    let (voter, other) = (Address::repeat_byte(2), Address::repeat_byte(3));
+    handle.witness(&[voter, other]);
+    handle.timestamp(5);
+    let ballot = Ballot::new(VOTED_APPROVE);
+    commit_test_vote(&hash, 2, &ballot);
+    commit_test_vote(&hash, 3, &Ballot::new(VOTED_REJECT));
+    assert_eq!(get_unrevealed_commitments(&hash), vec![voter, other]);
+    handle.timestamp(12);
+    assert!(reveal_vote(&hash, voter, ballot, b"salt"));
+    assert_eq!(get_unrevealed_commitments(&hash), vec![other]);
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(info.approve, 60);
+    assert_eq!(info.reject, 0);
+}
+
+#[test]
+#[should_panic(expected = "ballot does not match the commitment")]
+fn test_reveal_wrong_salt() {
+    let handle = build_runtime();
+    let hash = put_secret_topic(0);
+    let voter = Address::repeat_byte(2);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    let ballot = Ballot::new(VOTED_APPROVE);
+    commit_test_vote(&hash, 2, &ballot);
+    handle.timestamp(12);
+    reveal_vote(&hash, voter, ballot, b"pepper");
+}
+
+#[test]
+#[should_panic(expected = "not in the reveal period")]
+fn test_reveal_after_window() {
+    let handle = build_runtime();
+    let hash = put_secret_topic(0);
+    let voter = Address::repeat_byte(2);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    let ballot = Ballot::new(VOTED_APPROVE);
+    commit_test_vote(&hash, 2, &ballot);
+    handle.timestamp(15);
+    reveal_vote(&hash, voter, ballot, b"salt");
+}
+
+#[test]
+#[should_panic(expected = "not in the reveal period")]
+fn test_reveal_before_window() {
+    let handle = build_runtime();
+    let hash = put_secret_topic(0);
+    let voter = Address::repeat_byte(2);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    let ballot = Ballot::new(VOTED_APPROVE);
+    commit_test_vote(&hash, 2, &ballot);
+    reveal_vote(&hash, voter, ballot, b"salt");
+}
+
+/// a custom voters topic starting at 5 whose voters are not set yet
+fn put_allowlist_topic() -> H256 {
+    let hash = put_test_topic(0, 10);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.config.custom_voters = true;
+    info.start_time = 5;
+    put_topic_info(&hash, info);
+    hash
+}
+
+fn voter_weight(voter: u8, weight: U128) -> VoterWeight {
+    VoterWeight {
+        voter: Address::repeat_byte(voter),
+        weight,
+    }
+}
+
+#[test]
+fn test_set_voter_for_topic() {
+    let handle = build_runtime();
+    let hash = put_allowlist_topic();
+    handle.witness(&[Address::repeat_byte(1)]);
+    handle.timestamp(3);
+    assert!(set_voter_for_topic(
+        &hash,
+        vec![voter_weight(2, 60), voter_weight(3, 40)]
+    ));
+    handle.timestamp(6);
+    let voter = Address::repeat_byte(2);
+    check_node_voter(&hash, &voter);
+    // the weight assigned by the creator, not the stake of the node
+    assert_eq!(get_topic_voter_weight_by_hash(&hash, &voter), 60);
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(get_total_eligible_weight(&hash, &info), 100);
+}
+
+#[test]
+#[should_panic(expected = "not a voter of the topic")]
+fn test_voter_not_listed() {
+    let handle = build_runtime();
+    let hash = put_allowlist_topic();
+    handle.witness(&[Address::repeat_byte(1)]);
+    handle.timestamp(3);
+    assert!(set_voter_for_topic(&hash, vec![voter_weight(2, 60)]));
+    handle.timestamp(6);
+    check_node_voter(&hash, &Address::repeat_byte(3));
+}
+
+#[test]
+#[should_panic]
+fn test_set_voter_after_start() {
+    let handle = build_runtime();
+    let hash = put_allowlist_topic();
+    handle.witness(&[Address::repeat_byte(1)]);
+    handle.timestamp(5);
+    set_voter_for_topic(&hash, vec![voter_weight(2, 60)]);
+}
+
+#[test]
+#[should_panic(expected = "voter weight overflows")]
+fn test_voter_weight_overflow() {
+    let handle = build_runtime();
+    let hash = put_allowlist_topic();
+    handle.witness(&[Address::repeat_byte(1)]);
+    handle.timestamp(3);
+    set_voter_for_topic(&hash, vec![voter_weight(2, u64::MAX as U128 + 1)]);
+}
+
+#[test]
+#[should_panic(expected = "total weight overflows")]
+fn test_total_voter_weight_overflow() {
+    let handle = build_runtime();
+    let hash = put_allowlist_topic();
+    handle.witness(&[Address::repeat_byte(1)]);
+    handle.timestamp(3);
+    set_voter_for_topic(
+        &hash,
+        vec![voter_weight(2, u64::MAX as U128), voter_weight(3, 1)],
+    );
+}
+
+#[test]
+fn test_delegation_without_weight() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    handle.timestamp(5);
+    // voter 9 is not a voter of the topic, voter 3 delegated and did not vote
+    record_delegation_change(Address::repeat_byte(9), Address::repeat_byte(2));
+    record_delegation_change(Address::repeat_byte(3), Address::repeat_byte(2));
+    handle.timestamp(12);
+    let info = get_topic_info(&hash).unwrap();
+    let mut voted_info = vec![new_voted_info(2, 60, true, 0, false)];
+    apply_delegations(&hash, &info, &mut voted_info);
+    assert_eq!(voted_info[0].delegated.len(), 1);
+    assert_eq!(voted_info[0].delegated[0].voter, Address::repeat_byte(3));
+    assert_eq!(voted_info[0].node_count(), 2);
+    assert_eq!(voted_info[0].total_weight(), 100);
+}
+
+#[test]
+fn test_vote_after_split() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    let voter = Address::repeat_byte(2);
+    let mut split = new_voted_info(2, 60, false, 0, false);
+    split.shares = vec![50, 50];
+    put_voted_info(&hash, vec![split]);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    // the stored approve_or_reject of the split is false, rejecting is still a change
+    assert!(vote_topic(&hash, voter, false));
+    assert_eq!(get_voted_info(&hash, &voter), VOTED_REJECT);
+    assert_eq!(get_topic_info(&hash).unwrap().reject, 60);
+}
+
+#[test]
+fn test_vote_option_after_split() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.config.topic_type = TOPIC_TYPE_MULTI_CHOICE;
+    info.config.options = vec![b"plan a".to_vec(), b"plan b".to_vec()];
+    put_topic_info(&hash, info);
+    let voter = Address::repeat_byte(2);
+    let mut split = new_voted_info(2, 60, false, 0, false);
+    split.shares = vec![50, 50];
+    put_voted_info(&hash, vec![split]);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    assert!(vote_topic_option(&hash, voter, 0));
+    assert_eq!(get_topic_info(&hash).unwrap().tally.options, vec![60, 0]);
+}
+
+#[test]
+fn test_revoke_vote() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    let (voter, other) = (Address::repeat_byte(2), Address::repeat_byte(3));
+    handle.witness(&[voter, other]);
+    handle.timestamp(5);
+    assert!(vote_topic(&hash, voter, true));
+    assert!(vote_topic(&hash, other, false));
+    assert!(revoke_vote(&hash, voter));
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(info.approve, 0);
+    assert_eq!(info.reject, 40);
+    assert_eq!(info.tally.turnout, 40);
+    assert_eq!(get_voted_info(&hash, &voter), VOTED_NONE);
+    assert_eq!(get_voted_info(&hash, &other), VOTED_REJECT);
+    // once every vote is revoked the topic has no votes, nothing is read from elsewhere
+    assert!(revoke_vote(&hash, other));
+    assert!(get_voted_address(&hash).is_empty());
+    assert_eq!(get_voted_info(&hash, &other), VOTED_NONE);
+    assert_eq!(get_topic_info(&hash).unwrap().reject, 0);
+}
+
+#[test]
+#[should_panic(expected = "voting ended")]
+fn test_revoke_after_end() {
+    let handle = build_runtime();
+    let hash = put_custom_topic(0, 10);
+    let voter = Address::repeat_byte(2);
+    handle.witness(&[voter]);
+    handle.timestamp(5);
+    assert!(vote_topic(&hash, voter, true));
+    handle.timestamp(10);
+    revoke_vote(&hash, voter);
+}
+
+/// a custom voters topic with one action which passed, voting ended at 10
+/// the caller sets the time after 10 for finalizeTopic
+fn put_passed_topic(num: u32) -> H256 {
+    let hash = put_custom_topic(num, 10);
+    let mut info = get_topic_info(&hash).unwrap();
+    info.config.actions = vec![ProposalAction {
+        vm_type: VM_TYPE_WASM,
+        target: Address::repeat_byte(61),
+        method: b"setFee".to_vec(),
+        args: vec![],
+    }];
+    put_topic_info(&hash, info);
+    put_voted_info(&hash, vec![new_voted_info(2, 60, true, 0, false)]);
+    assert!(finalize_topic(&hash));
+    hash
+}
+
+#[test]
+#[should_panic(expected = "timelock not expired")]
+fn test_execute_before_eta() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.timestamp(eta - 1);
+    execute_topic(&hash);
+}
+
+#[test]
+fn test_execute_topic() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.on_contract_call(|target, _input| {
+        assert_eq!(target, &Address::repeat_byte(61));
+        Some(vec![])
+    });
+    handle.timestamp(eta);
+    assert!(execute_topic(&hash));
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(info.execution.executed_time, eta);
+    assert!(info.execution.success);
+    assert!(get_queued_topics().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "already executed")]
+fn test_execute_twice() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.on_contract_call(|_target, _input| Some(vec![]));
+    handle.timestamp(eta);
+    assert!(execute_topic(&hash));
+    execute_topic(&hash);
+}
+
+#[test]
+fn test_queue_topic() {
+    let handle = build_runtime();
+    set_veto_period(3600);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let info = get_topic_info(&hash).unwrap();
+    assert_eq!(info.execution.eta, 10 + DEFAULT_TIMELOCK_DELAY);
+    assert_eq!(get_queued_topics(), vec![hash]);
+    // the eta waits for the veto period when it is longer than the delay
+    set_veto_period(DEFAULT_VETO_PERIOD);
+    let later = put_passed_topic(1);
+    let info = get_topic_info(&later).unwrap();
+    assert_eq!(info.execution.eta, 10 + DEFAULT_VETO_PERIOD);
+}
+
+#[test]
+fn test_cancel_queued_topic() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.timestamp(eta - 1);
+    assert!(cancel_queued_topic(&hash, guardian));
+    let info = get_topic_info(&hash).unwrap();
+    assert!(info.execution.canceled);
+    assert_eq!(info.tally.result, RESULT_EXECUTION_CANCELED);
+    assert_eq!(get_topic_result(&hash), RESULT_EXECUTION_CANCELED);
+    assert!(get_queued_topics().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "timelock expired")]
+fn test_cancel_queued_topic_after_eta() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.timestamp(eta);
+    cancel_queued_topic(&hash, guardian);
+}
+
+#[test]
+#[should_panic(expected = "topic not passed")]
+fn test_execute_canceled_topic() {
+    let handle = build_runtime();
+    set_veto_period(0);
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    assert!(cancel_queued_topic(&hash, guardian));
+    handle.timestamp(eta);
+    execute_topic(&hash);
+}
+
+#[test]
+fn test_veto_topic() {
+    let handle = build_runtime();
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    assert!(veto_topic(&hash, guardian, b"malicious upgrade"));
+    assert_eq!(get_topic_result(&hash), RESULT_VETOED);
+    let veto = get_topic_veto(&hash).unwrap();
+    assert_eq!(veto.guardian, guardian);
+    assert_eq!(veto.reason, b"malicious upgrade".to_vec());
+    assert_eq!(veto.time, 12);
+    assert!(get_queued_topics().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "topic not passed")]
+fn test_execute_vetoed_topic() {
+    let handle = build_runtime();
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    assert!(veto_topic(&hash, guardian, b"malicious upgrade"));
+    let eta = get_topic_info(&hash).unwrap().execution.eta;
+    handle.timestamp(eta);
+    execute_topic(&hash);
+}
+
+#[test]
+#[should_panic(expected = "veto period ended")]
+fn test_veto_after_period() {
+    let handle = build_runtime();
+    let guardian = Address::repeat_byte(62);
+    add_guardian(&guardian);
+    handle.witness(&[guardian]);
+    handle.timestamp(12);
+    let hash = put_passed_topic(0);
+    handle.timestamp(10 + DEFAULT_VETO_PERIOD);
+    veto_topic(&hash, guardian, b"too late");
+}